@@ -0,0 +1,313 @@
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use bevy::audio::{AudioSink, AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::events::{GameOver, ItemCollected, LevelCompleted, PlayerDamaged, PlayerFell, PlayerJumped, PlayerLanded};
+use crate::{AppState, GameState, Player};
+
+pub const SAMPLE_RATE: u32 = 22050;
+pub const MUSIC_LAYERS: usize = 3;
+pub const MUSIC_BEAT: f32 = 0.5; // Seconds; 120 BPM
+pub const FULL_INTENSITY_ALTITUDE: f32 = 120.0; // Where level 1's music is at its fullest
+pub const INTENSITY_PER_LEVEL: f32 = 0.15;
+pub const MUSIC_FADE: f32 = 0.5; // Layer volume change per second
+
+// ===== SOUND EVENTS =====
+// Sent whether or not anything plays them, so headless runs can check which
+// sounds a scenario would make. All of them follow from the gameplay events.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundEvent {
+    Jump,
+    Land,
+    Collect,
+    HazardHit,
+    ShieldHit, // A hazard hit the shield absorbed
+    Fall,
+    LevelComplete,
+    GameOver,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 8] = [
+        SoundEvent::Jump,
+        SoundEvent::Land,
+        SoundEvent::Collect,
+        SoundEvent::HazardHit,
+        SoundEvent::ShieldHit,
+        SoundEvent::Fall,
+        SoundEvent::LevelComplete,
+        SoundEvent::GameOver,
+    ];
+}
+
+pub fn send_gameplay_sounds(
+    mut jumped: EventReader<PlayerJumped>,
+    mut landed: EventReader<PlayerLanded>,
+    mut collected: EventReader<ItemCollected>,
+    mut damaged: EventReader<PlayerDamaged>,
+    mut fell: EventReader<PlayerFell>,
+    mut completed: EventReader<LevelCompleted>,
+    mut game_over: EventReader<GameOver>,
+    mut sounds: EventWriter<SoundEvent>,
+) {
+    sounds.send_batch(jumped.read().map(|_| SoundEvent::Jump));
+    sounds.send_batch(landed.read().map(|_| SoundEvent::Land));
+    sounds.send_batch(collected.read().map(|_| SoundEvent::Collect));
+    sounds.send_batch(damaged.read().map(|event| {
+        if event.absorbed {
+            SoundEvent::ShieldHit
+        } else {
+            SoundEvent::HazardHit
+        }
+    }));
+    sounds.send_batch(fell.read().map(|_| SoundEvent::Fall));
+    sounds.send_batch(completed.read().map(|_| SoundEvent::LevelComplete));
+    sounds.send_batch(game_over.read().map(|_| SoundEvent::GameOver));
+}
+
+// ===== MUSIC INTENSITY =====
+// 0 on the ground of level 1, rising with the climb and with every level
+pub fn music_intensity(altitude: f32, level: u32) -> f32 {
+    let climb = (altitude / FULL_INTENSITY_ALTITUDE).max(0.0);
+    (climb + level.saturating_sub(1) as f32 * INTENSITY_PER_LEVEL).clamp(0.0, 1.0)
+}
+
+// The first layer always plays; the others fade in one after another as the
+// intensity rises
+pub fn layer_volume(layer: usize, intensity: f32) -> f32 {
+    if layer == 0 {
+        return 1.0;
+    }
+    let band = 1.0 / (MUSIC_LAYERS - 1) as f32;
+    ((intensity - (layer - 1) as f32 * band) / band).clamp(0.0, 1.0)
+}
+
+// ===== AUDIO PLUGIN =====
+// Plays `SoundEvent`s and the layered music. Every sound is synthesized at
+// startup, so there are no audio files to ship. Needs Bevy's audio output, so
+// only the windowed game adds it.
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, (create_sounds, start_music.after(create_sounds)))
+            .add_systems(Update, (play_sound_events, update_music));
+    }
+}
+
+#[derive(Resource)]
+pub struct SoundBank {
+    pub effects: HashMap<SoundEvent, Handle<AudioSource>>,
+    pub music: Vec<Handle<AudioSource>>, // One loop per layer
+}
+
+#[derive(Component)]
+pub struct MusicLayer {
+    pub layer: usize,
+    pub volume: f32, // Before the global volume
+}
+
+// ===== AUDIO SYSTEMS =====
+fn create_sounds(mut commands: Commands, mut sources: ResMut<Assets<AudioSource>>) {
+    let mut add = |samples: Vec<f32>| {
+        sources.add(AudioSource {
+            bytes: Arc::from(wav(&samples)),
+        })
+    };
+
+    let effects = SoundEvent::ALL
+        .iter()
+        .map(|event| (*event, add(effect_samples(*event))))
+        .collect();
+    let music = (0..MUSIC_LAYERS).map(|layer| add(music_samples(layer))).collect();
+    commands.insert_resource(SoundBank { effects, music });
+}
+
+// All layers start together so they stay in time; the upper ones start silent
+fn start_music(mut commands: Commands, bank: Res<SoundBank>) {
+    for (layer, source) in bank.music.iter().enumerate() {
+        let volume = layer_volume(layer, 0.0);
+        commands.spawn((
+            AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::LOOP.with_volume(Volume::new_relative(volume)),
+            },
+            MusicLayer { layer, volume },
+        ));
+    }
+}
+
+fn play_sound_events(mut commands: Commands, mut events: EventReader<SoundEvent>, bank: Res<SoundBank>) {
+    for event in events.read() {
+        if let Some(source) = bank.effects.get(event) {
+            commands.spawn(AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN,
+            });
+        }
+    }
+}
+
+fn update_music(
+    mut layer_query: Query<(&mut MusicLayer, &AudioSink)>,
+    player_query: Query<&Transform, With<Player>>,
+    game_state: Res<GameState>,
+    global_volume: Res<GlobalVolume>,
+    state: Res<State<AppState>>,
+    time: Res<Time>,
+) {
+    let altitude = player_query.get_single().map_or(0.0, |transform| transform.translation.y);
+    let intensity = music_intensity(altitude, game_state.level);
+    let paused = *state.get() == AppState::Paused;
+
+    for (mut music, sink) in &mut layer_query {
+        let target = layer_volume(music.layer, intensity);
+        let step = MUSIC_FADE * time.delta_seconds();
+        music.volume += (target - music.volume).clamp(-step, step);
+
+        // A sink's own volume ignores the global volume, so apply it here
+        sink.set_volume(music.volume * global_volume.volume.get());
+        if paused != sink.is_paused() {
+            if paused {
+                sink.pause();
+            } else {
+                sink.play();
+            }
+        }
+    }
+}
+
+// ===== SYNTHESIS =====
+#[derive(Clone, Copy, Debug)]
+enum Wave {
+    Sine,
+    Square,
+    Triangle,
+    Noise,
+}
+
+// A note sliding from `from` to `to` Hz over `seconds`, fading out as it goes
+fn tone(wave: Wave, from: f32, to: f32, seconds: f32, gain: f32) -> Vec<f32> {
+    let count = (seconds * SAMPLE_RATE as f32) as usize;
+    let mut phase = 0.0;
+    let mut noise = 0x2545_f491_u32;
+    (0..count)
+        .map(|index| {
+            let progress = index as f32 / count as f32;
+            phase = (phase + (from + (to - from) * progress) / SAMPLE_RATE as f32).fract();
+            let value = match wave {
+                Wave::Sine => (phase * TAU).sin(),
+                Wave::Square => {
+                    if phase < 0.5 {
+                        0.6
+                    } else {
+                        -0.6
+                    }
+                }
+                Wave::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+                Wave::Noise => {
+                    noise ^= noise << 13;
+                    noise ^= noise >> 17;
+                    noise ^= noise << 5;
+                    noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+                }
+            };
+            // Short attack so notes don't click, then a linear fade
+            let attack = (index as f32 / 64.0).min(1.0);
+            value * gain * attack * (1.0 - progress)
+        })
+        .collect()
+}
+
+fn notes(wave: Wave, frequencies: &[f32], seconds: f32, gain: f32) -> Vec<f32> {
+    frequencies
+        .iter()
+        .flat_map(|frequency| tone(wave, *frequency, *frequency, seconds, gain))
+        .collect()
+}
+
+fn mix(mut into: Vec<f32>, other: &[f32]) -> Vec<f32> {
+    if into.len() < other.len() {
+        into.resize(other.len(), 0.0);
+    }
+    for (sample, add) in into.iter_mut().zip(other) {
+        *sample += add;
+    }
+    into
+}
+
+fn effect_samples(event: SoundEvent) -> Vec<f32> {
+    match event {
+        SoundEvent::Jump => tone(Wave::Square, 300.0, 700.0, 0.15, 0.3),
+        SoundEvent::Land => tone(Wave::Sine, 140.0, 60.0, 0.1, 0.6),
+        SoundEvent::Collect => notes(Wave::Triangle, &[880.0, 1318.5], 0.08, 0.5),
+        SoundEvent::HazardHit => mix(
+            tone(Wave::Noise, 0.0, 0.0, 0.25, 0.4),
+            &tone(Wave::Square, 220.0, 80.0, 0.3, 0.3),
+        ),
+        SoundEvent::ShieldHit => notes(Wave::Sine, &[1046.5, 784.0], 0.1, 0.4),
+        SoundEvent::Fall => tone(Wave::Triangle, 600.0, 100.0, 0.8, 0.5),
+        SoundEvent::LevelComplete => notes(Wave::Square, &[523.3, 659.3, 784.0, 1046.5], 0.12, 0.3),
+        SoundEvent::GameOver => notes(Wave::Triangle, &[392.0, 329.6, 261.6, 196.0], 0.25, 0.5),
+    }
+}
+
+// Four chords of two beats each; every layer loops over the same eight beats
+const CHORDS: [[f32; 3]; 4] = [
+    [110.0, 130.8, 164.8], // A minor
+    [87.3, 110.0, 130.8],  // F major
+    [130.8, 164.8, 196.0], // C major
+    [98.0, 123.5, 146.8],  // G major
+];
+
+fn music_samples(layer: usize) -> Vec<f32> {
+    let chord_seconds = MUSIC_BEAT * 2.0;
+    let chord_len = (chord_seconds * SAMPLE_RATE as f32) as usize;
+    CHORDS
+        .iter()
+        .flat_map(|chord| {
+            let mut samples = chord_samples(layer, chord, chord_seconds);
+            samples.resize(chord_len, 0.0); // Keep the layers' loops exactly the same length
+            samples
+        })
+        .collect()
+}
+
+fn chord_samples(layer: usize, chord: &[f32; 3], chord_seconds: f32) -> Vec<f32> {
+    match layer {
+        // Held bass root
+        0 => tone(Wave::Sine, chord[0], chord[0], chord_seconds, 0.35),
+        // Eighth-note pulse an octave up
+        1 => notes(Wave::Square, &[chord[0] * 2.0; 4], MUSIC_BEAT / 2.0, 0.08),
+        // Sixteenth-note arpeggio two octaves up
+        _ => {
+            let arpeggio: Vec<f32> = chord.iter().chain(&chord[1..2]).map(|note| note * 4.0).collect();
+            notes(Wave::Triangle, &arpeggio.repeat(2), MUSIC_BEAT / 4.0, 0.12)
+        }
+    }
+}
+
+// 16-bit mono PCM
+fn wav(samples: &[f32]) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // Mono
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    bytes
+}
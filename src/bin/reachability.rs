@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use platformer_3d::difficulty::{apply_difficulty, Difficulty};
+use platformer_3d::generator::{generate_level, level_seed};
+use platformer_3d::level::{asset_dir, level_file, LevelData, LEVEL_FILE_COUNT};
+use platformer_3d::reachability::{analyze_level, JumpArc};
+use platformer_3d::tuning::{Tuning, TUNING_PATH};
+
+const USAGE: &str = "\
+Reports which platforms and collectibles can be reached from the spawn point.
+
+Usage: reachability [OPTIONS] [LEVEL_FILE...]
+
+With no level given, checks every built-in level file.
+
+Options:
+  --level <N>          Built-in level N; past the shipped files it is generated
+  --seed <SEED>        Run seed for generated levels [default: 0]
+  --difficulty <NAME>  Preset applied to built-in levels: casual, normal, extreme
+                       or nightmare [default: extreme]
+  --tuning <FILE>      Tuning file giving the player's speed and jump force
+                       [default: assets/game.tuning.ron]
+  --speed <F>          Player run speed [default: from the tuning file]
+  --jump-force <F>     Player jump velocity [default: from the tuning file]
+  --gravity <F>        Gravity magnitude [default: 9.81]
+  --safety <F>         Share of the jump arc a gap may use [default: 0.8]
+  -h, --help           Print this help
+
+Exits with 1 if any collectible is unreachable or a level has none, 2 on bad
+arguments or files.";
+
+enum LevelSource {
+    BuiltIn(u32),
+    File(PathBuf),
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+// Ok(true) when every level has collectibles and all of them are reachable
+fn run(args: &[String]) -> Result<bool, String> {
+    let mut arc = JumpArc::default();
+    let mut tuning_path = None;
+    let mut speed = None;
+    let mut jump_force = None;
+    let mut seed = 0;
+    let mut difficulty = Difficulty::default();
+    let mut sources = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--level" => sources.push(LevelSource::BuiltIn(parse(value("--level")?)?)),
+            "--seed" => seed = parse(value("--seed")?)?,
+            "--difficulty" => {
+                let name = value("--difficulty")?;
+                difficulty = Difficulty::from_name(name).ok_or_else(|| format!("unknown difficulty {}", name))?;
+            }
+            "--tuning" => tuning_path = Some(PathBuf::from(value("--tuning")?)),
+            "--speed" => speed = Some(parse(value("--speed")?)?),
+            "--jump-force" => jump_force = Some(parse(value("--jump-force")?)?),
+            "--gravity" => arc.gravity = parse(value("--gravity")?)?,
+            "--safety" => arc.safety = parse(value("--safety")?)?,
+            flag if flag.starts_with('-') => return Err(format!("unknown option {}", flag)),
+            path => sources.push(LevelSource::File(PathBuf::from(path))),
+        }
+    }
+    if sources.is_empty() {
+        sources.extend((1..=LEVEL_FILE_COUNT).map(LevelSource::BuiltIn));
+    }
+
+    // The game's own tuning file unless another is given; the compiled-in values
+    // only when the default file is missing
+    let (tuning, tuning_source) = match tuning_path {
+        Some(path) => (load(&path)?, path.display().to_string()),
+        None => {
+            let path = asset_dir().join(TUNING_PATH);
+            if path.exists() {
+                (load(&path)?, path.display().to_string())
+            } else {
+                (Tuning::default(), "built-in defaults".to_string())
+            }
+        }
+    };
+    arc.speed = speed.unwrap_or(tuning.player_speed);
+    arc.jump_force = jump_force.unwrap_or(tuning.jump_force);
+
+    println!("Tuning: {}", tuning_source);
+    println!(
+        "Jump arc: speed {} | jump force {} | gravity {} | apex {:.2} | flat reach {:.2} | safety {:.0}%",
+        arc.speed,
+        arc.jump_force,
+        arc.gravity,
+        arc.max_height(),
+        arc.max_reach(0.0).unwrap_or(0.0),
+        arc.safety * 100.0
+    );
+
+    let mut all_reachable = true;
+    for source in &sources {
+        let (name, data) = match source {
+            LevelSource::BuiltIn(level) if *level > LEVEL_FILE_COUNT => {
+                let mut data = generate_level(*level, level_seed(seed, *level), &arc);
+                apply_difficulty(difficulty, *level, &mut data, true);
+                (
+                    format!("Level {} (generated, seed {}, {})", level, seed, difficulty.name()),
+                    data,
+                )
+            }
+            LevelSource::BuiltIn(level) => {
+                let mut data = load(&level_file(*level))?;
+                apply_difficulty(difficulty, *level, &mut data, false);
+                (format!("Level {} ({})", level, difficulty.name()), data)
+            }
+            LevelSource::File(path) => (path.display().to_string(), load(path)?),
+        };
+        all_reachable &= report(&name, &data, &arc);
+    }
+
+    Ok(all_reachable)
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid number {}", value))
+}
+
+fn load<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let contents = std::fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+    ron::from_str(&contents).map_err(|err| format!("could not parse {}: {}", path.display(), err))
+}
+
+// Prints the report for one level; true when it has collectibles and every one of
+// them is reachable
+fn report(name: &str, data: &LevelData, arc: &JumpArc) -> bool {
+    let report = analyze_level(data, arc);
+    println!("\n=== {} - {} ===", name, data.name);
+
+    for standable in &report.standables {
+        let mark = if standable.reachable { "ok" } else { "UNREACHABLE" };
+        match (standable.timing_window, standable.cycle_seconds) {
+            (Some(window), Some(cycle)) => println!(
+                "  {:<22} {} (boardable {:.0}% of its {:.1}s cycle)",
+                standable.label,
+                mark,
+                window * 100.0,
+                cycle
+            ),
+            _ => println!("  {:<22} {}", standable.label, mark),
+        }
+    }
+
+    let unreachable: Vec<_> = report.unreachable_collectibles().collect();
+    println!(
+        "  Collectibles: {}/{} reachable",
+        report.collectibles.len() - unreachable.len(),
+        report.collectibles.len()
+    );
+    for collectible in &unreachable {
+        println!(
+            "  UNREACHABLE collectible at ({:.1}, {:.1}, {:.1}) worth {} points",
+            collectible.position.x, collectible.position.y, collectible.position.z, collectible.value
+        );
+    }
+
+    if report.collectibles.is_empty() {
+        println!("  NO collectibles: the level can never be completed");
+    }
+
+    !report.collectibles.is_empty() && unreachable.is_empty()
+}
@@ -0,0 +1,217 @@
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::controls::{ActionAxes, ActionSystem};
+use crate::{AppState, GameState, MainCamera, Player};
+
+// ===== CAMERA PLUGIN =====
+// Third-person camera orbiting the player. With no input it keeps the original
+// framing: behind the player on +Z, pulled back and up as the climb gets higher.
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraSettings>()
+            .add_systems(
+                PreUpdate,
+                orbit_camera.after(InputSystem).after(ActionSystem).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, camera_follow.run_if(not(in_state(AppState::Editor)))); // The editor flies it
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct CameraSettings {
+    pub mouse_sensitivity: f32, // Radians per pixel of mouse movement
+    pub stick_sensitivity: f32, // Radians per second at full camera stick or key
+    pub zoom_step: f32,         // Share of the distance per scroll line
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub min_pitch: f32, // Radians above the horizon
+    pub max_pitch: f32,
+    pub smoothing: f32,        // How quickly the camera catches up, per second
+    pub collision_margin: f32, // Distance kept from whatever blocks the view
+    pub framing: CameraFraming,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 0.005,
+            stick_sensitivity: 2.5,
+            zoom_step: 0.1,
+            min_zoom: 0.2,
+            max_zoom: 2.0,
+            min_pitch: 5f32.to_radians(),
+            max_pitch: 85f32.to_radians(),
+            smoothing: 2.0,
+            collision_margin: 0.5,
+            framing: CameraFraming::default(),
+        }
+    }
+}
+
+// Default offset behind and above the player, before any orbiting. Set from the
+// tuning file, so the numbers can be adjusted while the game runs.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct CameraFraming {
+    pub height: f32,
+    pub distance: f32,
+    pub altitude_scale: f32, // Player height at which the altitude terms below apply in full
+    pub height_per_altitude: f32,
+    pub distance_per_altitude: f32,
+    pub height_per_level: f32,
+    pub distance_per_level: f32,
+    pub look_above: f32, // How far above the player the camera aims
+    pub look_above_per_altitude: f32,
+}
+
+impl Default for CameraFraming {
+    fn default() -> Self {
+        Self {
+            height: 25.0,
+            distance: 40.0,
+            altitude_scale: 30.0,
+            height_per_altitude: 15.0,
+            distance_per_altitude: 20.0,
+            height_per_level: 5.0,
+            distance_per_level: 7.5,
+            look_above: 5.0,
+            look_above_per_altitude: 2.0,
+        }
+    }
+}
+
+// The player's adjustments on top of the default framing
+#[derive(Component, Clone, Copy, Debug)]
+pub struct OrbitCamera {
+    pub yaw: f32,          // Radians around the player; 0 looks down -Z
+    pub pitch_offset: f32, // Radians added to the default pitch
+    pub zoom: f32,         // Multiplier on the default distance
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch_offset: 0.0,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    // Turns input relative to the camera (-Z forward, +X right) into world space
+    pub fn relative(&self, direction: Vec3) -> Vec3 {
+        Quat::from_rotation_y(self.yaw) * direction
+    }
+}
+
+// ===== CAMERA SYSTEMS =====
+// Drag with the right mouse button or use the camera action (right stick by default)
+// to orbit, scroll to zoom
+pub fn orbit_camera(
+    mut camera_query: Query<&mut OrbitCamera>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    action_axes: Res<ActionAxes>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+) {
+    let mut turn = Vec2::ZERO;
+    if mouse_buttons.pressed(MouseButton::Right) {
+        turn += mouse_motion.read().map(|motion| motion.delta).sum::<Vec2>() * settings.mouse_sensitivity;
+    } else {
+        mouse_motion.clear();
+    }
+    let stick = Vec2::new(action_axes.camera.x, -action_axes.camera.y);
+    turn += stick * settings.stick_sensitivity * time.delta_seconds();
+
+    let scroll: f32 = mouse_wheel
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / 100.0,
+        })
+        .sum();
+
+    for mut orbit in &mut camera_query {
+        orbit.yaw -= turn.x;
+        orbit.pitch_offset = (orbit.pitch_offset + turn.y).clamp(-settings.max_pitch, settings.max_pitch);
+        orbit.zoom = (orbit.zoom * (1.0 - scroll * settings.zoom_step)).clamp(settings.min_zoom, settings.max_zoom);
+    }
+}
+
+// ===== ENHANCED CAMERA SYSTEM FOR EXTREME HEIGHTS =====
+fn camera_follow(
+    mut camera_query: Query<(&mut Transform, &OrbitCamera), (With<MainCamera>, Without<Player>)>,
+    player_query: Query<(Entity, &Transform), (With<Player>, Without<MainCamera>)>,
+    rapier_context: Res<RapierContext>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+    game_state: Res<GameState>,
+) {
+    if let (Ok((mut camera_transform, orbit)), Ok((player_entity, player_transform))) =
+        (camera_query.get_single_mut(), player_query.get_single())
+    {
+        // Dynamic camera positioning based on player height and level
+        let framing = &settings.framing;
+
+        // Scale camera distance based on player height for extreme levels
+        let height_factor = (player_transform.translation.y / framing.altitude_scale).max(0.0);
+        let level_factor = game_state.level as f32 - 1.0;
+
+        let height_offset = framing.height
+            + (height_factor * framing.height_per_altitude)
+            + (level_factor * framing.height_per_level);
+        let distance_offset = framing.distance
+            + (height_factor * framing.distance_per_altitude)
+            + (level_factor * framing.distance_per_level);
+
+        // Orbit around the player starting from that default framing
+        let pitch = (height_offset.atan2(distance_offset) + orbit.pitch_offset)
+            .clamp(settings.min_pitch, settings.max_pitch);
+        let direction = Quat::from_rotation_y(orbit.yaw) * Vec3::new(0.0, pitch.sin(), pitch.cos());
+        let mut distance = height_offset.hypot(distance_offset) * orbit.zoom;
+
+        // Pull in ahead of anything between the player and the camera
+        let blocked = rapier_context.cast_ray(
+            player_transform.translation,
+            direction,
+            distance,
+            true,
+            QueryFilter::new().exclude_rigid_body(player_entity).exclude_sensors(),
+        );
+        if let Some((_, toi)) = blocked {
+            distance = (toi - settings.collision_margin).max(settings.collision_margin);
+        }
+
+        // Smooth camera positioning, but never through a wall
+        let target_pos = player_transform.translation + direction * distance;
+        if blocked.is_some() {
+            camera_transform.translation = target_pos;
+        } else {
+            camera_transform.translation = camera_transform
+                .translation
+                .lerp(target_pos, (time.delta_seconds() * settings.smoothing).min(1.0));
+        }
+
+        // Look at player with slight upward offset for better visibility
+        let look_above = framing.look_above + height_factor * framing.look_above_per_altitude;
+        let look_target = player_transform.translation + Vec3::Y * look_above * orbit.zoom.min(1.0);
+        camera_transform.look_at(look_target, Vec3::Y);
+
+        // Debug camera info for extreme heights
+        if player_transform.translation.y > 80.0 {
+            if (time.elapsed_seconds() % 2.0) < 0.1 { // Print every 2 seconds
+                debug!("📹 Camera adjusted for extreme height: {:.1} (offset: {:.1})",
+                       player_transform.translation.y, height_offset);
+            }
+        }
+    }
+}
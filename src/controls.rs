@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::save::ProfilePath;
+
+// ===== CONTROLS PLUGIN =====
+// Maps keyboard and gamepad input onto game actions. Gameplay reads
+// `Input<Action>` and `ActionAxes` instead of raw devices, so bindings can be
+// changed in `bindings.ron` next to the profile.
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .init_resource::<Input<Action>>()
+            .init_resource::<ActionAxes>()
+            .init_resource::<ActionOverride>()
+            .add_systems(Startup, load_bindings)
+            .add_systems(PreUpdate, update_actions.in_set(ActionSystem).after(InputSystem));
+    }
+}
+
+// Actions are up to date after this set in `PreUpdate`
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionSystem;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Reset,
+    Pause,
+    CameraLeft,
+    CameraRight,
+    CameraUp,
+    CameraDown,
+}
+
+impl Action {
+    // Bitmask order used by `ActionFrame`
+    pub const ALL: [Action; 11] = [
+        Action::MoveForward,
+        Action::MoveBack,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Reset,
+        Action::Pause,
+        Action::CameraLeft,
+        Action::CameraRight,
+        Action::CameraUp,
+        Action::CameraDown,
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stick {
+    Left,
+    Right,
+}
+
+impl Stick {
+    fn axes(self) -> (GamepadAxisType, GamepadAxisType) {
+        match self {
+            Stick::Left => (GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY),
+            Stick::Right => (GamepadAxisType::RightStickX, GamepadAxisType::RightStickY),
+        }
+    }
+}
+
+// ===== BINDINGS =====
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct InputBindings {
+    pub keys: BTreeMap<Action, Vec<KeyCode>>,
+    pub gamepad_buttons: BTreeMap<Action, Vec<GamepadButtonType>>,
+    pub move_stick: Stick,
+    pub camera_stick: Stick,
+    pub stick_deadzone: f32, // Stick travel ignored around the center
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let keys = BTreeMap::from([
+            (Action::MoveForward, vec![KeyCode::W, KeyCode::Up]),
+            (Action::MoveBack, vec![KeyCode::S, KeyCode::Down]),
+            (Action::MoveLeft, vec![KeyCode::A, KeyCode::Left]),
+            (Action::MoveRight, vec![KeyCode::D, KeyCode::Right]),
+            (Action::Jump, vec![KeyCode::Space]),
+            (Action::Reset, vec![KeyCode::R]),
+            (Action::Pause, vec![KeyCode::Escape, KeyCode::P]),
+            (Action::CameraLeft, vec![]),
+            (Action::CameraRight, vec![]),
+            (Action::CameraUp, vec![]),
+            (Action::CameraDown, vec![]),
+        ]);
+        let gamepad_buttons = BTreeMap::from([
+            (Action::MoveForward, vec![GamepadButtonType::DPadUp]),
+            (Action::MoveBack, vec![GamepadButtonType::DPadDown]),
+            (Action::MoveLeft, vec![GamepadButtonType::DPadLeft]),
+            (Action::MoveRight, vec![GamepadButtonType::DPadRight]),
+            (Action::Jump, vec![GamepadButtonType::South]),
+            (Action::Reset, vec![GamepadButtonType::Select]),
+            (Action::Pause, vec![GamepadButtonType::Start]),
+        ]);
+
+        Self {
+            keys,
+            gamepad_buttons,
+            move_stick: Stick::Left,
+            camera_stick: Stick::Right,
+            stick_deadzone: 0.15,
+        }
+    }
+}
+
+impl InputBindings {
+    pub fn keys_for(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn buttons_for(&self, action: Action) -> &[GamepadButtonType] {
+        self.gamepad_buttons.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+// ===== ACTION STATE =====
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct ActionAxes {
+    pub movement: Vec2, // +X right, +Y forward; length up to 1, less for a half-tilted stick
+    pub camera: Vec2,   // +X orbit right, +Y tilt up
+}
+
+// Everything gameplay needs to reproduce a frame of input
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActionFrame {
+    pub pressed: u16, // Bit i set = Action::ALL[i] held
+    pub movement: Vec2,
+}
+
+impl ActionFrame {
+    pub fn capture(actions: &Input<Action>, axes: &ActionAxes) -> Self {
+        let pressed = Action::ALL
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| actions.pressed(**action))
+            .fold(0, |mask, (i, _)| mask | (1 << i));
+        Self {
+            pressed,
+            movement: axes.movement,
+        }
+    }
+
+    pub fn is_pressed(&self, action: Action) -> bool {
+        Action::ALL
+            .iter()
+            .position(|candidate| *candidate == action)
+            .is_some_and(|i| self.pressed & (1 << i) != 0)
+    }
+}
+
+// Stands in for the devices on the next update; set by replay playback
+#[derive(Resource, Default)]
+pub struct ActionOverride(pub Option<ActionFrame>);
+
+// ===== ACTION SYSTEMS =====
+fn update_actions(
+    bindings: Res<InputBindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut action_override: ResMut<ActionOverride>,
+    mut actions: ResMut<Input<Action>>,
+    mut axes: ResMut<ActionAxes>,
+) {
+    actions.clear();
+
+    if let Some(frame) = action_override.0.take() {
+        for action in Action::ALL {
+            set_action(&mut actions, action, frame.is_pressed(action));
+        }
+        axes.movement = frame.movement;
+        axes.camera = Vec2::ZERO;
+        return;
+    }
+
+    for action in Action::ALL {
+        let held = bindings.keys_for(action).iter().any(|key| keyboard_input.pressed(*key))
+            || gamepads.iter().any(|gamepad| {
+                bindings
+                    .buttons_for(action)
+                    .iter()
+                    .any(|button| gamepad_buttons.pressed(GamepadButton::new(gamepad, *button)))
+            });
+        set_action(&mut actions, action, held);
+    }
+
+    let digital = |positive: Action, negative: Action| {
+        actions.pressed(positive) as i32 as f32 - actions.pressed(negative) as i32 as f32
+    };
+    let keys_move = Vec2::new(
+        digital(Action::MoveRight, Action::MoveLeft),
+        digital(Action::MoveForward, Action::MoveBack),
+    )
+    .normalize_or_zero();
+    let keys_camera = Vec2::new(
+        digital(Action::CameraRight, Action::CameraLeft),
+        digital(Action::CameraUp, Action::CameraDown),
+    )
+    .normalize_or_zero();
+
+    // Whichever is pushed further wins: keys, D-pad or the strongest stick
+    let strongest = |stick: Stick, fallback: Vec2| {
+        gamepads
+            .iter()
+            .map(|gamepad| read_stick(&gamepad_axes, gamepad, stick, bindings.stick_deadzone))
+            .fold(fallback, |best, value| if value.length() > best.length() { value } else { best })
+    };
+    axes.movement = strongest(bindings.move_stick, keys_move);
+    axes.camera = strongest(bindings.camera_stick, keys_camera);
+}
+
+fn set_action(actions: &mut Input<Action>, action: Action, held: bool) {
+    if held {
+        actions.press(action);
+    } else {
+        actions.release(action);
+    }
+}
+
+// Stick position with the deadzone cut out and the rest rescaled to 0..1
+fn read_stick(axes: &Axis<GamepadAxis>, gamepad: Gamepad, stick: Stick, deadzone: f32) -> Vec2 {
+    let (x, y) = stick.axes();
+    let raw = Vec2::new(
+        axes.get(GamepadAxis::new(gamepad, x)).unwrap_or(0.0),
+        axes.get(GamepadAxis::new(gamepad, y)).unwrap_or(0.0),
+    );
+    let length = raw.length();
+    if length <= deadzone {
+        return Vec2::ZERO;
+    }
+    raw / length * ((length - deadzone) / (1.0 - deadzone)).min(1.0)
+}
+
+// ===== PERSISTENCE =====
+fn bindings_path(profile_path: &ProfilePath) -> Option<PathBuf> {
+    Some(profile_path.0.as_ref()?.parent()?.join("bindings.ron"))
+}
+
+// Reads the player's bindings, writing out the defaults on first run so they can be edited
+fn load_bindings(profile_path: Res<ProfilePath>, mut bindings: ResMut<InputBindings>) {
+    let Some(path) = bindings_path(&profile_path) else {
+        return;
+    };
+    match fs::read_to_string(&path) {
+        Ok(contents) => match ron::from_str::<InputBindings>(&contents) {
+            Ok(loaded) => {
+                info!("🎮 Loaded input bindings from {}", path.display());
+                *bindings = loaded;
+            }
+            Err(err) => warn!("Ignoring unreadable bindings {}: {}", path.display(), err),
+        },
+        Err(_) => save_bindings(&profile_path, &bindings),
+    }
+}
+
+pub fn save_bindings(profile_path: &ProfilePath, bindings: &InputBindings) {
+    let Some(path) = bindings_path(profile_path) else {
+        return;
+    };
+    let contents = match ron::ser::to_string_pretty(bindings, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Could not serialize bindings: {}", err);
+            return;
+        }
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create config directory {}: {}", dir.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = fs::write(&path, contents) {
+        warn!("Could not write bindings {}: {}", path.display(), err);
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::{ActiveLevel, LevelData};
+use crate::{GameState, LevelEntity};
+
+// Things at or below this height count as ground level and are never raised or lowered
+pub const GROUND_LEVEL_HEIGHT: f32 = 5.0;
+// Speed of the first moving platform on level 1, whatever the preset
+pub const BASE_PLATFORM_SPEED: f32 = 6.0;
+
+// ===== DIFFICULTY PRESETS =====
+// Level files are authored at `Extreme`; other presets rescale them as they spawn.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    Casual,
+    Normal,
+    #[default]
+    Extreme,
+    Nightmare,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DifficultySettings {
+    pub height_step: f32,   // Height multiplier gained per level
+    pub size_step: f32,     // Platform size lost per level
+    pub size_floor: f32,    // Smallest platform size multiplier
+    pub speed_step: f32,    // Moving platform speed gained per level
+    pub hazard_share: f32,  // Share of each level's hazards that spawn
+    pub hazard_size: f32,   // Hazard size multiplier, hitbox included
+    pub starting_lives: u32,
+    pub invulnerability: f32, // Seconds of safety after a hazard hit
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Casual,
+        Difficulty::Normal,
+        Difficulty::Extreme,
+        Difficulty::Nightmare,
+    ];
+
+    pub const fn settings(self) -> DifficultySettings {
+        match self {
+            Difficulty::Casual => DifficultySettings {
+                height_step: 1.0,
+                size_step: 0.1,
+                size_floor: 0.6,
+                speed_step: 1.0,
+                hazard_share: 0.4,
+                hazard_size: 1.0,
+                starting_lives: 5,
+                invulnerability: 4.0,
+            },
+            Difficulty::Normal => DifficultySettings {
+                height_step: 1.75,
+                size_step: 0.2,
+                size_floor: 0.35,
+                speed_step: 2.0,
+                hazard_share: 0.7,
+                hazard_size: 1.0,
+                starting_lives: 4,
+                invulnerability: 3.0,
+            },
+            Difficulty::Extreme => DifficultySettings {
+                height_step: 2.5,
+                size_step: 0.35,
+                size_floor: 0.15,
+                speed_step: 3.0,
+                hazard_share: 1.0,
+                hazard_size: 1.0,
+                starting_lives: 3,
+                invulnerability: 2.5,
+            },
+            Difficulty::Nightmare => DifficultySettings {
+                height_step: 3.0,
+                size_step: 0.4,
+                size_floor: 0.1,
+                speed_step: 4.0,
+                hazard_share: 1.0,
+                hazard_size: 1.3,
+                starting_lives: 1,
+                invulnerability: 1.5,
+            },
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Casual => "Casual",
+            Difficulty::Normal => "Normal",
+            Difficulty::Extreme => "Extreme",
+            Difficulty::Nightmare => "Nightmare",
+        }
+    }
+
+    // Case-insensitive, for the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+    }
+
+    // Index in `ALL`, as stored in replays
+    pub fn index(self) -> u8 {
+        Self::ALL.iter().position(|difficulty| *difficulty == self).unwrap_or(0) as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    // Wraps around, for cycling on the title menu
+    pub fn cycle(self, step: i32) -> Self {
+        let count = Self::ALL.len() as i32;
+        Self::ALL[(self.index() as i32 + step).rem_euclid(count) as usize]
+    }
+}
+
+impl DifficultySettings {
+    pub fn height_multiplier(&self, level: u32) -> f32 {
+        1.0 + (level as f32 - 1.0) * self.height_step
+    }
+
+    pub fn size_multiplier(&self, level: u32) -> f32 {
+        (1.0 - (level as f32 - 1.0) * self.size_step).max(self.size_floor)
+    }
+
+    pub fn platform_speed(&self, level: u32) -> f32 {
+        BASE_PLATFORM_SPEED + (level as f32 - 1.0) * self.speed_step
+    }
+}
+
+// ===== LEVEL SCALING =====
+// Rescales a level authored at `Extreme` to `difficulty`. Generated levels are
+// already checked against the player's jump, so only their hazards change.
+pub fn apply_difficulty(difficulty: Difficulty, level: u32, data: &mut LevelData, generated: bool) {
+    let settings = difficulty.settings();
+    thin_hazards(data, settings.hazard_share);
+    for hazard in &mut data.hazards {
+        hazard.size = hazard.size.map(|d| d * settings.hazard_size);
+    }
+    if generated || difficulty == Difficulty::Extreme {
+        return;
+    }
+
+    let authored = Difficulty::Extreme.settings();
+    let height = settings.height_multiplier(level) / authored.height_multiplier(level);
+    let size = settings.size_multiplier(level) / authored.size_multiplier(level);
+    let speed = settings.platform_speed(level) / authored.platform_speed(level);
+
+    let raise = |position: &mut [f32; 3]| {
+        if position[1] > GROUND_LEVEL_HEIGHT {
+            position[1] *= height;
+        }
+    };
+    let resize = |dimensions: &mut [f32; 3]| *dimensions = dimensions.map(|d| d * size);
+
+    for platform in &mut data.platforms {
+        raise(&mut platform.position);
+        resize(&mut platform.size);
+    }
+    for platform in &mut data.moving_platforms {
+        raise(&mut platform.start);
+        raise(&mut platform.end);
+        platform.waypoints.iter_mut().for_each(raise);
+        resize(&mut platform.size);
+        platform.speed *= speed;
+    }
+    data.collectibles.iter_mut().for_each(|collectible| raise(&mut collectible.position));
+    data.hazards.iter_mut().for_each(|hazard| raise(&mut hazard.position));
+    data.checkpoints.iter_mut().for_each(|checkpoint| raise(&mut checkpoint.position));
+    data.power_ups.iter_mut().for_each(|power_up| raise(&mut power_up.position));
+}
+
+// Keeps `share` of the hazards, spread evenly through the list
+fn thin_hazards(data: &mut LevelData, share: f32) {
+    if share >= 1.0 {
+        return;
+    }
+    let mut index = 0.0;
+    data.hazards.retain(|_| {
+        let keep = ((index + 1.0) * share).floor() > (index * share).floor();
+        index += 1.0;
+        keep
+    });
+}
+
+// ===== DIFFICULTY CHANGES =====
+// Picking another preset on the title menu gives the fresh run its lives and
+// respawns the waiting level at the new scale
+pub fn apply_difficulty_change(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    mut game_state: ResMut<GameState>,
+    mut active_level: ResMut<ActiveLevel>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+) {
+    game_state.lives = difficulty.settings().starting_lives;
+    if active_level.pending.is_some() {
+        return; // Not spawned yet; it will use the new preset
+    }
+
+    for entity in &level_entities {
+        commands.entity(entity).despawn();
+    }
+    active_level.pending = Some(game_state.level);
+}
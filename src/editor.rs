@@ -0,0 +1,867 @@
+use std::fs;
+
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::controls::ActionAxes;
+use crate::difficulty::{Difficulty, BASE_PLATFORM_SPEED};
+use crate::level::{
+    level_file, spawn_level_content, ActiveLevel, CheckpointData, CollectibleData, HazardData, LevelData,
+    LevelLibrary, MovingPlatformData, PlatformData, PowerUpData, LEVEL_FILE_COUNT,
+};
+use crate::platform::PlatformKind;
+use crate::powerup::{PowerUpKind, POWER_UP_RADIUS};
+use crate::save::RunClock;
+use crate::speedrun::RunSplits;
+use crate::{AppState, GameState, LevelEntity, MainCamera, COLLECTIBLE_RADIUS};
+
+pub const EDITOR_FLY_SPEED: f32 = 15.0; // Units per second; Shift for four times that
+pub const EDITOR_LOOK_SENSITIVITY: f32 = 0.004; // Radians per pixel
+pub const MIN_EDITOR_SIZE: f32 = 0.1;
+pub const HANDLE_SIZE: f32 = 0.6;
+
+// ===== EDITOR PLUGIN =====
+// Level editor for the shipped level files, opened with E on the title menu.
+// Edits `LevelData` directly and rebuilds the scene with `spawn_level_content`,
+// so what you see is what spawns in play. Needs a window, so only the windowed
+// binary adds it.
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelEditor>()
+            .add_systems(OnEnter(AppState::Editor), enter_editor)
+            .add_systems(OnExit(AppState::Editor), despawn_editor_hud)
+            .add_systems(OnEnter(AppState::MainMenu), end_play_test)
+            .add_systems(
+                Update,
+                (
+                    open_editor.run_if(in_state(AppState::MainMenu)),
+                    return_from_play_test.run_if(in_state(AppState::Playing)),
+                    (
+                        load_editor_level,
+                        fly_editor_camera,
+                        editor_mouse.after(fly_editor_camera),
+                        editor_keys.after(editor_mouse),
+                        rebuild_editor_scene.after(editor_keys).after(load_editor_level),
+                        draw_editor_gizmos.after(editor_keys),
+                        update_editor_hud.after(editor_keys),
+                    )
+                        .run_if(in_state(AppState::Editor)),
+                ),
+            );
+    }
+}
+
+// ===== EDITOR STATE =====
+// Something in the level that can be selected, moved and deleted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EditorItem {
+    SpawnPoint,
+    Platform(usize),
+    MovingPlatform(usize),
+    PathPoint(usize, usize), // Moving platform, then point along its path: start, waypoints, end
+    Collectible(usize),
+    Hazard(usize),
+    Checkpoint(usize),
+    PowerUp(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DragMode {
+    Move,           // Across the horizontal plane through the item
+    MoveVertical,   // Shift
+    Resize,         // Ctrl: width and depth
+    ResizeVertical, // Ctrl + Shift: height
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Drag {
+    pub item: EditorItem,
+    pub mode: DragMode,
+    pub plane_y: f32,
+    pub last_point: Vec3,  // Cursor on the drag plane last frame
+    pub last_cursor: Vec2, // Screen position last frame
+}
+
+#[derive(Resource)]
+pub struct LevelEditor {
+    pub level: u32,
+    pub data: Option<LevelData>, // None until the level file has loaded
+    pub selected: Option<EditorItem>,
+    pub drag: Option<Drag>,
+    pub dirty: bool,   // Scene needs rebuilding from `data`
+    pub unsaved: bool, // `data` differs from the file on disk
+    pub play_testing: bool,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub message: String, // Last save or error, shown on the HUD
+}
+
+impl Default for LevelEditor {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            data: None,
+            selected: None,
+            drag: None,
+            dirty: false,
+            unsaved: false,
+            play_testing: false,
+            yaw: 0.0,
+            pitch: -0.4,
+            message: String::new(),
+        }
+    }
+}
+
+// ===== LEVEL DATA EDITING =====
+fn path_points(platform: &MovingPlatformData) -> Vec<[f32; 3]> {
+    let mut points = vec![platform.start];
+    points.extend(platform.waypoints.iter().copied());
+    points.push(platform.end);
+    points
+}
+
+fn path_point_mut(platform: &mut MovingPlatformData, point: usize) -> Option<&mut [f32; 3]> {
+    let waypoints = platform.waypoints.len();
+    match point {
+        0 => Some(&mut platform.start),
+        i if i <= waypoints => platform.waypoints.get_mut(i - 1),
+        i if i == waypoints + 1 => Some(&mut platform.end),
+        _ => None,
+    }
+}
+
+// Center and full size of the box used for picking and drawing
+pub fn item_bounds(data: &LevelData, item: EditorItem) -> Option<(Vec3, Vec3)> {
+    let handle = Vec3::splat(HANDLE_SIZE);
+    match item {
+        EditorItem::SpawnPoint => Some((Vec3::from(data.spawn_point), Vec3::splat(1.0))),
+        EditorItem::Platform(i) => data
+            .platforms
+            .get(i)
+            .map(|platform| (Vec3::from(platform.position), Vec3::from(platform.size))),
+        EditorItem::MovingPlatform(i) => data
+            .moving_platforms
+            .get(i)
+            .map(|platform| (Vec3::from(platform.start), Vec3::from(platform.size))),
+        EditorItem::PathPoint(i, point) => {
+            let points = path_points(data.moving_platforms.get(i)?);
+            points.get(point).map(|point| (Vec3::from(*point), handle))
+        }
+        EditorItem::Collectible(i) => data
+            .collectibles
+            .get(i)
+            .map(|collectible| (Vec3::from(collectible.position), Vec3::splat(COLLECTIBLE_RADIUS * 2.0))),
+        EditorItem::Hazard(i) => data
+            .hazards
+            .get(i)
+            .map(|hazard| (Vec3::from(hazard.position), Vec3::from(hazard.size))),
+        EditorItem::Checkpoint(i) => data
+            .checkpoints
+            .get(i)
+            .map(|checkpoint| (Vec3::from(checkpoint.position) + Vec3::Y, Vec3::new(0.4, 2.0, 0.4))),
+        EditorItem::PowerUp(i) => data
+            .power_ups
+            .get(i)
+            .map(|power_up| (Vec3::from(power_up.position), Vec3::splat(POWER_UP_RADIUS * 2.0))),
+    }
+}
+
+fn offset(position: &mut [f32; 3], delta: Vec3) {
+    *position = (Vec3::from(*position) + delta).to_array();
+}
+
+// Moving a moving platform carries its whole path along
+pub fn translate_item(data: &mut LevelData, item: EditorItem, delta: Vec3) {
+    match item {
+        EditorItem::SpawnPoint => offset(&mut data.spawn_point, delta),
+        EditorItem::Platform(i) => {
+            if let Some(platform) = data.platforms.get_mut(i) {
+                offset(&mut platform.position, delta);
+            }
+        }
+        EditorItem::MovingPlatform(i) => {
+            if let Some(platform) = data.moving_platforms.get_mut(i) {
+                offset(&mut platform.start, delta);
+                offset(&mut platform.end, delta);
+                platform.waypoints.iter_mut().for_each(|point| offset(point, delta));
+            }
+        }
+        EditorItem::PathPoint(i, point) => {
+            let platform = data.moving_platforms.get_mut(i);
+            if let Some(position) = platform.and_then(|platform| path_point_mut(platform, point)) {
+                offset(position, delta);
+            }
+        }
+        EditorItem::Collectible(i) => {
+            if let Some(collectible) = data.collectibles.get_mut(i) {
+                offset(&mut collectible.position, delta);
+            }
+        }
+        EditorItem::Hazard(i) => {
+            if let Some(hazard) = data.hazards.get_mut(i) {
+                offset(&mut hazard.position, delta);
+            }
+        }
+        EditorItem::Checkpoint(i) => {
+            if let Some(checkpoint) = data.checkpoints.get_mut(i) {
+                offset(&mut checkpoint.position, delta);
+            }
+        }
+        EditorItem::PowerUp(i) => {
+            if let Some(power_up) = data.power_ups.get_mut(i) {
+                offset(&mut power_up.position, delta);
+            }
+        }
+    }
+}
+
+// Only boxes can be resized
+pub fn item_size_mut(data: &mut LevelData, item: EditorItem) -> Option<&mut [f32; 3]> {
+    match item {
+        EditorItem::Platform(i) => data.platforms.get_mut(i).map(|platform| &mut platform.size),
+        EditorItem::MovingPlatform(i) => data.moving_platforms.get_mut(i).map(|platform| &mut platform.size),
+        EditorItem::Hazard(i) => data.hazards.get_mut(i).map(|hazard| &mut hazard.size),
+        _ => None,
+    }
+}
+
+// Returns false for things that can't be removed: the spawn point and a path's two ends
+pub fn remove_item(data: &mut LevelData, item: EditorItem) -> bool {
+    fn remove<T>(items: &mut Vec<T>, i: usize) -> bool {
+        let exists = i < items.len();
+        if exists {
+            items.remove(i);
+        }
+        exists
+    }
+
+    match item {
+        EditorItem::SpawnPoint => false,
+        EditorItem::Platform(i) => remove(&mut data.platforms, i),
+        EditorItem::MovingPlatform(i) => remove(&mut data.moving_platforms, i),
+        EditorItem::PathPoint(i, point) => data
+            .moving_platforms
+            .get_mut(i)
+            .is_some_and(|platform| point > 0 && remove(&mut platform.waypoints, point - 1)),
+        EditorItem::Collectible(i) => remove(&mut data.collectibles, i),
+        EditorItem::Hazard(i) => remove(&mut data.hazards, i),
+        EditorItem::Checkpoint(i) => remove(&mut data.checkpoints, i),
+        EditorItem::PowerUp(i) => remove(&mut data.power_ups, i),
+    }
+}
+
+// Every pickable item; path handles only for the selected moving platform
+fn all_items(data: &LevelData, selected: Option<EditorItem>) -> Vec<EditorItem> {
+    let mut items = vec![EditorItem::SpawnPoint];
+    items.extend((0..data.platforms.len()).map(EditorItem::Platform));
+    items.extend((0..data.moving_platforms.len()).map(EditorItem::MovingPlatform));
+    items.extend((0..data.collectibles.len()).map(EditorItem::Collectible));
+    items.extend((0..data.hazards.len()).map(EditorItem::Hazard));
+    items.extend((0..data.checkpoints.len()).map(EditorItem::Checkpoint));
+    items.extend((0..data.power_ups.len()).map(EditorItem::PowerUp));
+    if let Some(EditorItem::MovingPlatform(i) | EditorItem::PathPoint(i, _)) = selected {
+        if let Some(platform) = data.moving_platforms.get(i) {
+            items.extend((0..platform.waypoints.len() + 2).map(|point| EditorItem::PathPoint(i, point)));
+        }
+    }
+    items
+}
+
+// Distance along the ray to an axis-aligned box, if it is hit
+fn ray_box(ray: Ray, center: Vec3, size: Vec3) -> Option<f32> {
+    let inverse = ray.direction.recip();
+    let a = (center - size / 2.0 - ray.origin) * inverse;
+    let b = (center + size / 2.0 - ray.origin) * inverse;
+    let near = a.min(b).max_element();
+    let far = a.max(b).min_element();
+    (far >= near.max(0.0)).then_some(near.max(0.0))
+}
+
+// Nearest item under the ray; path handles win over the platform they sit in
+fn pick(data: &LevelData, selected: Option<EditorItem>, ray: Ray) -> Option<(EditorItem, f32)> {
+    all_items(data, selected)
+        .into_iter()
+        .filter_map(|item| {
+            let (center, size) = item_bounds(data, item)?;
+            ray_box(ray, center, size).map(|distance| (item, distance))
+        })
+        .min_by(|(a, da), (b, db)| {
+            let handle = |item: &EditorItem| !matches!(item, EditorItem::PathPoint(..));
+            handle(a).cmp(&handle(b)).then(da.total_cmp(db))
+        })
+}
+
+// Where a new item goes: on top of the platform under the cursor, else on the ground
+fn placement_point(data: &LevelData, ray: Ray) -> Option<Vec3> {
+    let on_platform = data
+        .platforms
+        .iter()
+        .filter_map(|platform| {
+            let (center, size) = (Vec3::from(platform.position), Vec3::from(platform.size));
+            ray_box(ray, center, size).map(|distance| (distance, center.y + size.y / 2.0))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+    match on_platform {
+        Some((distance, top)) => {
+            let hit = ray.get_point(distance);
+            Some(Vec3::new(hit.x, top, hit.z))
+        }
+        None => ray.intersect_plane(Vec3::ZERO, Vec3::Y).map(|distance| ray.get_point(distance)),
+    }
+}
+
+// Adds an item of the kind bound to `key` with its base at `point`; returns it
+fn place_item(data: &mut LevelData, key: KeyCode, point: Vec3) -> Option<EditorItem> {
+    match key {
+        KeyCode::Key1 => {
+            data.platforms.push(PlatformData {
+                position: (point + Vec3::Y * 0.25).to_array(),
+                size: [4.0, 0.5, 4.0],
+                color: [0.5, 0.5, 0.6],
+                emissive: None,
+                kind: PlatformKind::Solid,
+            });
+            Some(EditorItem::Platform(data.platforms.len() - 1))
+        }
+        KeyCode::Key2 => {
+            let start = point + Vec3::Y * 0.25;
+            data.moving_platforms.push(MovingPlatformData {
+                start: start.to_array(),
+                end: (start + Vec3::X * 6.0).to_array(),
+                size: [3.0, 0.5, 3.0],
+                speed: BASE_PLATFORM_SPEED,
+                color: [0.8, 0.5, 0.2],
+                ..default()
+            });
+            Some(EditorItem::MovingPlatform(data.moving_platforms.len() - 1))
+        }
+        KeyCode::Key3 => {
+            data.collectibles.push(CollectibleData {
+                position: (point + Vec3::Y).to_array(),
+                value: 10,
+                color: [1.0, 0.84, 0.0],
+                emissive: Some([0.5, 0.4, 0.0]),
+            });
+            Some(EditorItem::Collectible(data.collectibles.len() - 1))
+        }
+        KeyCode::Key4 => {
+            data.hazards.push(HazardData {
+                position: (point + Vec3::Y * 0.5).to_array(),
+                size: [1.0, 1.0, 1.0],
+                color: [1.0, 0.0, 0.0],
+                emissive: Some([0.5, 0.0, 0.0]),
+            });
+            Some(EditorItem::Hazard(data.hazards.len() - 1))
+        }
+        KeyCode::Key5 => {
+            data.checkpoints.push(CheckpointData { position: point.to_array() });
+            Some(EditorItem::Checkpoint(data.checkpoints.len() - 1))
+        }
+        KeyCode::Key6 => {
+            data.power_ups.push(PowerUpData {
+                position: (point + Vec3::Y).to_array(),
+                kind: PowerUpKind::DoubleJump,
+                duration: None,
+            });
+            Some(EditorItem::PowerUp(data.power_ups.len() - 1))
+        }
+        _ => None,
+    }
+}
+
+// ===== ENTERING AND LEAVING =====
+fn open_editor(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut editor: ResMut<LevelEditor>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::E) {
+        editor.play_testing = false;
+        next_state.set(AppState::Editor);
+    }
+}
+
+// Takes over the scene: the normal level spawn stands down while editing
+fn enter_editor(
+    mut commands: Commands,
+    mut editor: ResMut<LevelEditor>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut game_state: ResMut<GameState>,
+    mut splits: ResMut<RunSplits>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+) {
+    active_level.pending = None;
+    editor.drag = None;
+    editor.dirty = true;
+    discard_play_test(&mut editor, &mut game_state, &mut splits);
+    if let Ok(transform) = camera_query.get_single() {
+        let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        editor.yaw = yaw;
+        editor.pitch = pitch;
+    }
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        EditorHud,
+    ));
+}
+
+fn despawn_editor_hud(mut commands: Commands, hud_query: Query<Entity, With<EditorHud>>) {
+    for entity in &hud_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn load_editor_level(
+    mut editor: ResMut<LevelEditor>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<LevelData>>,
+) {
+    if editor.data.is_none() {
+        if let Some(data) = library.get(editor.level).and_then(|handle| levels.get(handle)) {
+            editor.data = Some(data.clone());
+            editor.selected = None;
+            editor.unsaved = false;
+            editor.dirty = true;
+        }
+    }
+}
+
+// Edits stay in memory for the session until saved to the file
+fn store_in_library(editor: &LevelEditor, library: &LevelLibrary, levels: &mut Assets<LevelData>) {
+    if let (Some(data), Some(handle)) = (&editor.data, library.get(editor.level)) {
+        if let Some(level) = levels.get_mut(handle) {
+            *level = data.clone();
+        }
+    }
+}
+
+// A fresh run of the edited level; no lives lost to testing carry over
+fn start_play_test(
+    editor: &mut LevelEditor,
+    game_state: &mut GameState,
+    active_level: &mut ActiveLevel,
+    difficulty: Difficulty,
+) {
+    editor.play_testing = true;
+    game_state.level = editor.level;
+    game_state.score = 0;
+    game_state.lives = difficulty.settings().starting_lives;
+    active_level.pending = Some(editor.level);
+}
+
+// Run condition for record keeping: runs through an edited level never reach the
+// player's profile, leaderboard, splits or ghosts
+pub fn not_play_testing(editor: Option<Res<LevelEditor>>) -> bool {
+    !editor.is_some_and(|editor| editor.play_testing)
+}
+
+fn end_play_test(mut editor: ResMut<LevelEditor>, mut game_state: ResMut<GameState>, mut splits: ResMut<RunSplits>) {
+    discard_play_test(&mut editor, &mut game_state, &mut splits);
+}
+
+// Leaves nothing of a play-test behind for the next real run to record
+fn discard_play_test(editor: &mut LevelEditor, game_state: &mut GameState, splits: &mut RunSplits) {
+    if std::mem::take(&mut editor.play_testing) {
+        game_state.score = 0;
+        *splits = RunSplits::default();
+    }
+}
+
+// F5 during a play-test goes straight back to editing
+fn return_from_play_test(
+    keyboard_input: Res<Input<KeyCode>>,
+    editor: Res<LevelEditor>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if editor.play_testing && keyboard_input.just_pressed(KeyCode::F5) {
+        next_state.set(AppState::Editor);
+    }
+}
+
+// ===== CAMERA =====
+// Hold the right mouse button to look around; WASD / arrows to fly, E / Q up and down
+fn fly_editor_camera(
+    mut editor: ResMut<LevelEditor>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    action_axes: Res<ActionAxes>,
+    time: Res<Time>,
+) {
+    let look: Vec2 = mouse_motion.read().map(|motion| motion.delta).sum();
+    if mouse_buttons.pressed(MouseButton::Right) {
+        editor.yaw -= look.x * EDITOR_LOOK_SENSITIVITY;
+        editor.pitch = (editor.pitch - look.y * EDITOR_LOOK_SENSITIVITY).clamp(-1.5, 1.5);
+    }
+
+    let Ok(mut transform) = camera_query.get_single_mut() else {
+        return;
+    };
+    transform.rotation = Quat::from_euler(EulerRot::YXZ, editor.yaw, editor.pitch, 0.0);
+
+    let vertical = keyboard_input.pressed(KeyCode::E) as i32 as f32 - keyboard_input.pressed(KeyCode::Q) as i32 as f32;
+    let direction = transform.rotation * Vec3::new(action_axes.movement.x, 0.0, -action_axes.movement.y)
+        + Vec3::Y * vertical;
+    let boost = if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        4.0
+    } else {
+        1.0
+    };
+    transform.translation += direction * EDITOR_FLY_SPEED * boost * time.delta_seconds();
+}
+
+// ===== MOUSE EDITING =====
+// Click to select, drag to move; Shift drags vertically, Ctrl resizes
+fn editor_mouse(
+    mut editor: ResMut<LevelEditor>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (window_query.get_single(), camera_query.get_single()) else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+    let editor = &mut *editor;
+    let Some(data) = editor.data.as_mut() else {
+        return;
+    };
+
+    if mouse_buttons.just_released(MouseButton::Left) && editor.drag.take().is_some() {
+        editor.dirty = true;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        editor.selected = pick(data, editor.selected, ray).map(|(item, _)| item);
+        let Some((item, (center, _))) = editor.selected.and_then(|item| Some((item, item_bounds(data, item)?))) else {
+            return;
+        };
+        let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        let mode = match (ctrl, shift) {
+            (false, false) => DragMode::Move,
+            (false, true) => DragMode::MoveVertical,
+            (true, false) => DragMode::Resize,
+            (true, true) => DragMode::ResizeVertical,
+        };
+        let last_point = ray
+            .intersect_plane(center, Vec3::Y)
+            .map_or(center, |distance| ray.get_point(distance));
+        editor.drag = Some(Drag {
+            item,
+            mode,
+            plane_y: center.y,
+            last_point,
+            last_cursor: cursor,
+        });
+        return;
+    }
+
+    let Some(drag) = editor.drag.as_mut() else {
+        return;
+    };
+    let Some((center, _)) = item_bounds(data, drag.item) else {
+        editor.drag = None;
+        return;
+    };
+    let point = ray
+        .intersect_plane(Vec3::Y * drag.plane_y, Vec3::Y)
+        .map_or(drag.last_point, |distance| ray.get_point(distance));
+    // Screen movement scaled so the item keeps up with the cursor at its distance
+    let rise = (drag.last_cursor.y - cursor.y) * camera_transform.translation().distance(center) * 0.002;
+
+    match drag.mode {
+        DragMode::Move => translate_item(data, drag.item, point - drag.last_point),
+        DragMode::MoveVertical => translate_item(data, drag.item, Vec3::Y * rise),
+        DragMode::Resize => {
+            if let Some(size) = item_size_mut(data, drag.item) {
+                let grow = ((point - center).abs() - (drag.last_point - center).abs()) * 2.0;
+                size[0] = (size[0] + grow.x).max(MIN_EDITOR_SIZE);
+                size[2] = (size[2] + grow.z).max(MIN_EDITOR_SIZE);
+            }
+        }
+        DragMode::ResizeVertical => {
+            if let Some(size) = item_size_mut(data, drag.item) {
+                size[1] = (size[1] + rise).max(MIN_EDITOR_SIZE);
+            }
+        }
+    }
+    drag.last_point = point;
+    drag.last_cursor = cursor;
+    editor.unsaved = true;
+}
+
+// ===== KEYBOARD COMMANDS =====
+fn editor_keys(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut editor: ResMut<LevelEditor>,
+    library: Res<LevelLibrary>,
+    mut levels: ResMut<Assets<LevelData>>,
+    mut game_state: ResMut<GameState>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut clock: ResMut<RunClock>,
+    mut splits: ResMut<RunSplits>,
+    difficulty: Res<Difficulty>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    // Leaving keeps the edits for this session; the run starts over
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        store_in_library(&editor, &library, &mut levels);
+        for entity in &level_entities {
+            commands.entity(entity).despawn();
+        }
+        game_state.level = 1;
+        game_state.score = 0;
+        game_state.lives = difficulty.settings().starting_lives;
+        active_level.pending = Some(1);
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F5) {
+        store_in_library(&editor, &library, &mut levels);
+        for entity in &level_entities {
+            commands.entity(entity).despawn();
+        }
+        *clock = RunClock::default();
+        *splits = RunSplits::default();
+        start_play_test(&mut editor, &mut game_state, &mut active_level, *difficulty);
+        next_state.set(AppState::Playing);
+        return;
+    }
+
+    // Page Up / Page Down switch between the level files
+    let switch =
+        keyboard_input.just_pressed(KeyCode::PageDown) as i32 - keyboard_input.just_pressed(KeyCode::PageUp) as i32;
+    if switch != 0 {
+        store_in_library(&editor, &library, &mut levels);
+        editor.level = (editor.level as i32 - 1 + switch).rem_euclid(LEVEL_FILE_COUNT as i32) as u32 + 1;
+        editor.data = None;
+        editor.drag = None;
+        return;
+    }
+
+    if ctrl && keyboard_input.just_pressed(KeyCode::S) {
+        let path = level_file(editor.level);
+        let Some(data) = &editor.data else {
+            return;
+        };
+        let result = ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| fs::write(&path, contents).map_err(|err| err.to_string()));
+        editor.message = match result {
+            Ok(()) => {
+                editor.unsaved = false;
+                format!("Saved {}", path.display())
+            }
+            Err(err) => format!("Could not save {}: {}", path.display(), err),
+        };
+        info!("🛠️ {}", editor.message);
+        return;
+    }
+
+    let cursor_ray = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor, (camera, transform))| camera.viewport_to_world(transform, cursor));
+    let editor = &mut *editor;
+    let Some(data) = editor.data.as_mut() else {
+        return;
+    };
+    let mut changed = false;
+
+    if keyboard_input.any_just_pressed([KeyCode::Delete, KeyCode::Back]) {
+        if let Some(item) = editor.selected {
+            changed = remove_item(data, item);
+            editor.selected = None;
+            editor.drag = None;
+        }
+    }
+
+    // N adds a path point before the end of the selected moving platform's path
+    if keyboard_input.just_pressed(KeyCode::N) {
+        if let Some(EditorItem::MovingPlatform(i) | EditorItem::PathPoint(i, _)) = editor.selected {
+            if let Some(platform) = data.moving_platforms.get_mut(i) {
+                let points = path_points(platform);
+                let before_end = Vec3::from(points[points.len() - 2]);
+                let middle = before_end.lerp(Vec3::from(platform.end), 0.5);
+                platform.waypoints.push(middle.to_array());
+                editor.selected = Some(EditorItem::PathPoint(i, platform.waypoints.len()));
+                changed = true;
+            }
+        }
+    }
+
+    if let Some(point) = cursor_ray.and_then(|ray| placement_point(data, ray)) {
+        if keyboard_input.just_pressed(KeyCode::P) {
+            data.spawn_point = (point + Vec3::Y * 2.0).to_array();
+            editor.selected = Some(EditorItem::SpawnPoint);
+            changed = true;
+        }
+        let placed = keyboard_input
+            .get_just_pressed()
+            .find_map(|key| place_item(data, *key, point));
+        if placed.is_some() {
+            editor.selected = placed;
+            changed = true;
+        }
+    }
+
+    if changed {
+        editor.dirty = true;
+        editor.unsaved = true;
+    }
+}
+
+// ===== SCENE =====
+// Respawns the level through the same code as play; waits while dragging
+fn rebuild_editor_scene(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut editor: ResMut<LevelEditor>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+) {
+    if !editor.dirty || editor.drag.is_some() {
+        return;
+    }
+    let Some(data) = &editor.data else {
+        return;
+    };
+
+    for entity in &level_entities {
+        commands.entity(entity).despawn();
+    }
+    spawn_level_content(&mut commands, &mut meshes, &mut materials, editor.level, data);
+    editor.dirty = false;
+}
+
+fn draw_editor_gizmos(mut gizmos: Gizmos, editor: Res<LevelEditor>) {
+    let Some(data) = &editor.data else {
+        return;
+    };
+
+    for item in all_items(data, editor.selected) {
+        let Some((center, size)) = item_bounds(data, item) else {
+            continue;
+        };
+        let color = if editor.selected == Some(item) {
+            Color::YELLOW
+        } else {
+            match item {
+                EditorItem::SpawnPoint => Color::LIME_GREEN,
+                EditorItem::Platform(_) => Color::WHITE,
+                EditorItem::MovingPlatform(_) | EditorItem::PathPoint(..) => Color::CYAN,
+                EditorItem::Collectible(_) => Color::GOLD,
+                EditorItem::Hazard(_) => Color::RED,
+                EditorItem::Checkpoint(_) => Color::GREEN,
+                EditorItem::PowerUp(i) => data.power_ups[i].kind.color(),
+            }
+        };
+        match item {
+            EditorItem::Collectible(_) | EditorItem::PowerUp(_) | EditorItem::SpawnPoint => {
+                gizmos.sphere(center, Quat::IDENTITY, size.x / 2.0, color);
+            }
+            _ => gizmos.cuboid(Transform::from_translation(center).with_scale(size), color),
+        }
+    }
+
+    // Paths of every moving platform, with the platform's size at the far end
+    for platform in &data.moving_platforms {
+        let points = path_points(platform);
+        for pair in points.windows(2) {
+            gizmos.line(Vec3::from(pair[0]), Vec3::from(pair[1]), Color::CYAN);
+        }
+        gizmos.cuboid(
+            Transform::from_translation(Vec3::from(platform.end)).with_scale(Vec3::from(platform.size)),
+            Color::CYAN.with_a(0.4),
+        );
+    }
+}
+
+// ===== HUD =====
+#[derive(Component)]
+pub struct EditorHud;
+
+const EDITOR_HELP: [&str; 3] = [
+    "Right drag look | WASD fly | E/Q up/down | Click select | Drag move | Shift+drag height | Ctrl+drag resize",
+    "1 platform | 2 moving platform | 3 collectible | 4 hazard | 5 checkpoint | 6 power-up | P spawn point",
+    "N path point | Del delete | PgUp/PgDn level | Ctrl+S save | F5 play-test (F5 again to return) | Esc menu",
+];
+
+fn describe(data: &LevelData, item: EditorItem) -> String {
+    let Some((center, size)) = item_bounds(data, item) else {
+        return String::new();
+    };
+    let name = match item {
+        EditorItem::SpawnPoint => "Spawn point".to_string(),
+        EditorItem::Platform(i) => format!("Platform {} ({:?})", i + 1, data.platforms[i].kind),
+        EditorItem::MovingPlatform(i) => format!("Moving platform {}", i + 1),
+        EditorItem::PathPoint(i, point) => format!("Moving platform {} path point {}", i + 1, point + 1),
+        EditorItem::Collectible(i) => format!("Collectible {} ({} points)", i + 1, data.collectibles[i].value),
+        EditorItem::Hazard(i) => format!("Hazard {}", i + 1),
+        EditorItem::Checkpoint(i) => format!("Checkpoint {}", i + 1),
+        EditorItem::PowerUp(i) => format!("{} power-up", data.power_ups[i].kind.label()),
+    };
+    match item {
+        EditorItem::Platform(_) | EditorItem::MovingPlatform(_) | EditorItem::Hazard(_) => format!(
+            "{} at ({:.2}, {:.2}, {:.2}) size {:.2}×{:.2}×{:.2}",
+            name, center.x, center.y, center.z, size.x, size.y, size.z
+        ),
+        _ => format!("{} at ({:.2}, {:.2}, {:.2})", name, center.x, center.y, center.z),
+    }
+}
+
+fn update_editor_hud(editor: Res<LevelEditor>, mut hud_query: Query<&mut Text, With<EditorHud>>) {
+    let status = match &editor.data {
+        Some(data) => format!(
+            "🛠️ EDITING Level {} - {}{}",
+            editor.level,
+            data.name,
+            if editor.unsaved { " (unsaved)" } else { "" }
+        ),
+        None => format!("🛠️ Loading Level {}...", editor.level),
+    };
+    let selected = editor
+        .selected
+        .zip(editor.data.as_ref())
+        .map(|(item, data)| describe(data, item))
+        .unwrap_or_default();
+
+    for mut text in &mut hud_query {
+        text.sections[0].value = format!("{}\n{}\n{}\n{}", status, selected, editor.message, EDITOR_HELP.join("\n"));
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::powerup::PowerUpKind;
+
+// ===== GAMEPLAY EVENTS =====
+// Sent by the gameplay systems at every moment that matters, for the HUD, audio,
+// stats, the console log and tests to subscribe to. `GamePlugin` registers them.
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerJumped {
+    pub air_jump: bool, // A double jump rather than one off the ground
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerLanded {
+    pub fall_speed: f32, // Downward speed on touching down
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ItemCollected {
+    pub value: u32,  // The collectible's own value
+    pub points: u32, // After the combo multiplier
+    pub combo: u32,  // Place in the current chain, 1 for a lone pickup
+    pub bonus_lives: u32,
+    pub position: Vec3,
+}
+
+// A hazard hit; with a shield charge to spare no life is lost
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerDamaged {
+    pub hazard_position: Vec3,
+    pub absorbed: bool,
+    pub lives: u32, // Left after the hit
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerFell {
+    pub depth: f32, // How far below the world the player was caught
+    pub lives: u32, // Left after the fall
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LevelCompleted {
+    pub level: u32,
+    pub bonus_points: u32,
+    pub bonus_lives: u32, // From the completion bonus and thresholds passed on the way
+    pub score: u32,
+    pub lives: u32,
+}
+
+// Sent before the state is reset, with the run being thrown away
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GameReset {
+    pub level: u32,
+    pub score: u32,
+    pub lives: u32,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GameOver {
+    pub level: u32,
+    pub score: u32,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CheckpointReached {
+    pub respawn_point: Vec3,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PowerUpCollected {
+    pub kind: PowerUpKind,
+    pub duration: f32,
+}
+
+// A level cleared faster than ever before
+#[derive(Event, Clone, Copy, Debug)]
+pub struct NewBestTime {
+    pub level: u32,
+    pub seconds: f32,
+}
+
+// A finished run that made the leaderboard
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LeaderboardRank {
+    pub rank: usize, // 1 for the top
+    pub score: u32,
+}
+
+// A run whose splits beat the personal best for its difficulty
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PersonalBest {
+    pub difficulty: Difficulty,
+    pub levels: usize,
+    pub seconds: f32,
+}
+
+// The fastest clear of a level, now raced as its ghost
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GhostSaved {
+    pub difficulty: Difficulty,
+    pub level: u32,
+}
+
+// The records a finished run can set, together so the systems that end a run
+// stay within Bevy's system parameter limit
+#[derive(SystemParam)]
+pub struct RunRecordEvents<'w> {
+    pub ranks: EventWriter<'w, LeaderboardRank>,
+    pub personal_bests: EventWriter<'w, PersonalBest>,
+}
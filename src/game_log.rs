@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use crate::events::{
+    CheckpointReached, GameOver, GameReset, GhostSaved, ItemCollected, LeaderboardRank, LevelCompleted, NewBestTime,
+    PersonalBest, PlayerDamaged, PlayerFell, PowerUpCollected,
+};
+use crate::save::format_time;
+
+// ===== GAME LOG PLUGIN =====
+// Narrates the gameplay events to the console through `bevy::log`. Optional:
+// the game plays the same without it.
+pub struct GameLogPlugin;
+
+impl Plugin for GameLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                log_collected,
+                log_damaged,
+                log_fell,
+                log_level_completed,
+                log_game_reset,
+                log_game_over,
+                log_checkpoint,
+                log_power_up,
+                log_new_best,
+                log_leaderboard_rank,
+                log_personal_best,
+                log_ghost_saved,
+            ),
+        );
+    }
+}
+
+// ===== LOG SYSTEMS =====
+fn log_collected(mut events: EventReader<ItemCollected>) {
+    for event in events.read() {
+        if event.combo > 1 {
+            info!("🔥 {}x COMBO! +{} points", event.combo, event.points);
+        }
+        // Special messages for high-value collectibles
+        if event.value >= 30 {
+            info!("💎 EXTREME collectible worth {} points! Height bonus!", event.value);
+        }
+        if event.bonus_lives > 0 {
+            info!("🌟 BONUS LIFE! +{}", event.bonus_lives);
+        }
+    }
+}
+
+fn log_damaged(mut events: EventReader<PlayerDamaged>) {
+    for event in events.read() {
+        if event.absorbed {
+            info!("🛡️ Shield absorbed the hit!");
+            continue;
+        }
+
+        if event.hazard_position.y > 80.0 {
+            info!("💀 EXTREME ALTITUDE HAZARD HIT! Lives: {}", event.lives);
+        } else if event.hazard_position.y > 30.0 {
+            info!("⚠️ Elevated hazard hit! Lives: {}", event.lives);
+        } else {
+            info!("🔥 Ground hazard hit! Lives: {}", event.lives);
+        }
+
+        // Game over warning
+        if event.lives == 1 {
+            warn!("🚨 LAST LIFE! One more mistake and it's over!");
+        } else if event.lives == 0 {
+            info!("💀 GAME OVER! Press R to try the extreme challenge again!");
+        }
+    }
+}
+
+fn log_fell(mut events: EventReader<PlayerFell>) {
+    for event in events.read() {
+        match event.depth as u32 {
+            30..=60 => info!("💥 Fell into the abyss! Lives: {}", event.lives),
+            61..=100 => info!("🌪️ EXTREME fall from great height! Lives: {}", event.lives),
+            101.. => info!("☄️ CATASTROPHIC fall from impossible heights! Lives: {}", event.lives),
+            _ => info!("💀 Fell off the world! Lives: {}", event.lives),
+        }
+
+        if event.lives == 0 {
+            info!("💀 ELIMINATED! The extreme challenge claims another victim!");
+        }
+    }
+}
+
+fn log_level_completed(mut events: EventReader<LevelCompleted>) {
+    for event in events.read() {
+        match event.level {
+            1 => info!("🎯 LEVEL 1 CONQUERED! Welcome to the nightmare..."),
+            2 => info!("🔥 LEVEL 2 MASTERED! The challenge intensifies!"),
+            3 => info!("💀 LEVEL 3 DEMOLISHED! Extreme heights await!"),
+            4 => info!("👑 LEVEL 4 ANNIHILATED! You're entering legend territory!"),
+            _ => info!("🏆 LEVEL {} OBLITERATED! PLATFORMING DEITY STATUS!", event.level),
+        }
+        info!("🚀 Preparing for EXTREME Level {}...", event.level + 1);
+        info!(
+            "🎁 Level completion bonus: +{} lives, +{} points!",
+            event.bonus_lives, event.bonus_points
+        );
+        info!("📊 Total: {} points, {} lives", event.score, event.lives);
+    }
+}
+
+fn log_game_reset(mut events: EventReader<GameReset>) {
+    for event in events.read() {
+        info!("🔄 RESETTING EXTREME CHALLENGE...");
+        info!("📊 Previous session stats:");
+        info!("   🏆 Reached Level: {}", event.level);
+        info!("   💰 Final Score: {}", event.score);
+        info!("   ❤️ Lives Remaining: {}", event.lives);
+
+        // Motivational reset messages
+        match (event.level, event.score) {
+            (1, 0..=50) => info!("🌱 Fresh start! The extreme challenge awaits!"),
+            (2..=3, _) => info!("💪 Good attempt! Ready to conquer the heights again?"),
+            (4..=5, _) => info!("🎯 Impressive progress! Time to claim the summit!"),
+            (_, 200..) => info!("🏆 LEGENDARY performance! One more try for perfection!"),
+            _ => info!("🔥 Back to the grind! Show these platforms who's boss!"),
+        }
+
+        info!("✨ EXTREME CHALLENGE RESET COMPLETE! ✨");
+        info!("🎮 Controls: WASD/Arrows to move, Space to jump");
+        info!("💎 Collect all items to advance to next level");
+        info!("⚠️ Avoid hazards and don't fall into the abyss!");
+        info!("🏔️ Each level gets exponentially more difficult!");
+    }
+}
+
+fn log_game_over(mut events: EventReader<GameOver>) {
+    for event in events.read() {
+        info!("💀 GAME OVER at Level {} with {} points", event.level, event.score);
+    }
+}
+
+fn log_checkpoint(mut events: EventReader<CheckpointReached>) {
+    for event in events.read() {
+        info!("🚩 Checkpoint reached at height {:.1}!", event.respawn_point.y);
+    }
+}
+
+fn log_power_up(mut events: EventReader<PowerUpCollected>) {
+    for event in events.read() {
+        info!("✨ {} for {:.0}s!", event.kind.label(), event.duration);
+    }
+}
+
+fn log_new_best(mut events: EventReader<NewBestTime>) {
+    for event in events.read() {
+        info!("⏱️ NEW BEST on Level {}: {}", event.level, format_time(event.seconds));
+    }
+}
+
+fn log_leaderboard_rank(mut events: EventReader<LeaderboardRank>) {
+    for event in events.read() {
+        info!("🏆 Leaderboard rank #{} with {} points!", event.rank, event.score);
+    }
+}
+
+fn log_personal_best(mut events: EventReader<PersonalBest>) {
+    for event in events.read() {
+        info!(
+            "⏱️ NEW {} PERSONAL BEST: {} levels in {}",
+            event.difficulty.name(),
+            event.levels,
+            format_time(event.seconds)
+        );
+    }
+}
+
+fn log_ghost_saved(mut events: EventReader<GhostSaved>) {
+    for event in events.read() {
+        info!("👻 New {} ghost for Level {}", event.difficulty.name(), event.level);
+    }
+}
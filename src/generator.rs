@@ -0,0 +1,485 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+use bevy::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::level::{
+    CheckpointData, CollectibleData, HazardData, LevelData, LightingData, MovingPlatformData, PlatformData,
+    PointLightData,
+};
+use crate::platform::PlatformKind;
+use crate::reachability::{can_jump, JumpArc, Surface};
+use crate::PLAYER_RADIUS;
+
+// The static ground spawned in `setup_game`; every chain starts from it
+pub const GROUND_CENTER: [f32; 3] = [0.0, -0.5, 0.0];
+pub const GROUND_SIZE: [f32; 3] = [25.0, 1.0, 25.0];
+
+// Chains curl back toward the middle once they drift this far out
+const MAX_CHAIN_RADIUS: f32 = 30.0;
+const MIN_GAP: f32 = 0.5;
+const PLATFORM_THICKNESS: f32 = 0.4;
+const HAZARD_SIZE: f32 = 1.5;
+// A hazard hits once the player touches its box, up to its corner at the largest preset
+// size (Nightmare's); the route keeps another player's width clear of that
+const HAZARD_CLEARANCE: f32 =
+    HAZARD_SIZE / 2.0 * SQRT_2 * Difficulty::Nightmare.settings().hazard_size + PLAYER_RADIUS * 3.0;
+const PLACEMENT_ATTEMPTS: u32 = 16;
+
+// ===== RANDOM NUMBERS =====
+// SplitMix64: tiny and identical on every platform, so a seed always builds the same level
+#[derive(Clone, Debug)]
+pub struct LevelRng(u64);
+
+impl LevelRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+// Each level of a run gets its own stream from the run seed
+pub fn level_seed(run_seed: u64, level: u32) -> u64 {
+    LevelRng::new(run_seed ^ (level as u64).wrapping_mul(0xD1B5_4A32_D192_ED03)).next_u64()
+}
+
+// ===== GENERATOR SETTINGS =====
+#[derive(Clone, Copy, Debug)]
+pub struct GeneratorSettings {
+    pub platform_count: usize,
+    pub platform_size: f32,
+    pub gap_usage: f32, // Share of the safe jump arc each gap may use
+    pub moving_platforms: usize,
+    pub hazards: usize,
+    pub collectibles: usize,
+    pub checkpoint_every: usize,
+}
+
+impl GeneratorSettings {
+    // Longer chains, smaller tops and wider gaps as levels go up; endless levels plateau
+    pub fn for_level(level: u32) -> Self {
+        let difficulty = level.saturating_sub(1) as f32;
+
+        Self {
+            platform_count: 12 + 2 * level.min(15) as usize,
+            platform_size: (3.0 - difficulty * 0.3).max(1.2),
+            gap_usage: (0.5 + difficulty * 0.08).min(0.95),
+            moving_platforms: (1 + level as usize / 2).min(6),
+            hazards: (4 + 2 * level as usize).min(30),
+            collectibles: (6 + level as usize).min(16),
+            checkpoint_every: 6,
+        }
+    }
+}
+
+// ===== LEVEL GENERATION =====
+// Builds a level whose every platform-to-platform jump fits inside `arc`
+pub fn generate_level(level: u32, seed: u64, arc: &JumpArc) -> LevelData {
+    let settings = GeneratorSettings::for_level(level);
+    let mut rng = LevelRng::new(seed);
+
+    let chain = generate_chain(&mut rng, arc, &settings);
+    let summit = chain.last().map_or(0.0, Surface::top);
+
+    let platforms = chain
+        .iter()
+        .enumerate()
+        .map(|(i, surface)| {
+            let t = i as f32 / chain.len().max(2).saturating_sub(1) as f32;
+            let color = [0.3 + 0.5 * t, 0.6 - 0.3 * t, 0.3];
+            PlatformData {
+                position: surface.center.to_array(),
+                size: surface.size.to_array(),
+                color,
+                emissive: glow(surface.center.y, color),
+                kind: PlatformKind::Solid,
+            }
+        })
+        .collect();
+
+    let (moving_platforms, far_ends) = generate_moving_platforms(&mut rng, arc, &settings, &chain);
+    let collectibles = place_collectibles(level, &settings, &chain, &far_ends);
+    let hazards = place_hazards(&mut rng, &settings, &chain, &moving_platforms);
+
+    let checkpoints = chain
+        .iter()
+        .skip(settings.checkpoint_every - 1)
+        .step_by(settings.checkpoint_every)
+        .map(|surface| CheckpointData {
+            position: [surface.center.x, surface.top(), surface.center.z],
+        })
+        .collect();
+
+    let point_lights = chain
+        .iter()
+        .step_by(8)
+        .map(|surface| PointLightData {
+            position: (surface.center + Vec3::Y * 10.0).to_array(),
+            intensity: 8000.0,
+            range: 60.0,
+        })
+        .collect();
+
+    LevelData {
+        name: format!("Generated Level {} (seed {:#018x})", level, seed),
+        spawn_point: [0.0, 2.0, 0.0],
+        lighting: LightingData {
+            sun_illuminance: 15000.0,
+            sun_position: [0.0, summit + 20.0, 0.0],
+            point_lights,
+        },
+        platforms,
+        moving_platforms,
+        collectibles,
+        hazards,
+        checkpoints,
+        power_ups: Vec::new(),
+    }
+}
+
+// Emissive glow for anything high up, like the hand-made levels
+fn glow(height: f32, color: [f32; 3]) -> Option<[f32; 3]> {
+    (height > 50.0).then(|| color.map(|channel| channel * 0.3))
+}
+
+// Ascending chain of static platforms starting from the ground
+fn generate_chain(rng: &mut LevelRng, arc: &JumpArc, settings: &GeneratorSettings) -> Vec<Surface> {
+    let mut chain = Vec::with_capacity(settings.platform_count);
+    let mut current = Surface::new(GROUND_CENTER, GROUND_SIZE);
+    let mut heading = rng.range(0.0, TAU);
+
+    for _ in 0..settings.platform_count {
+        let side = settings.platform_size * rng.range(0.8, 1.2);
+        let size = Vec3::new(side, PLATFORM_THICKNESS, side);
+
+        // Turn back toward the middle when drifting too far out
+        let flat = Vec2::new(current.center.x, current.center.z);
+        heading = if flat.length() > MAX_CHAIN_RADIUS {
+            (-flat.y).atan2(-flat.x) + rng.range(-0.5, 0.5)
+        } else {
+            heading + rng.range(-0.9, 0.9)
+        };
+
+        let next = place_next(rng, arc, settings, &current, size, heading);
+        chain.push(next);
+        current = next;
+    }
+
+    chain
+}
+
+// One validated jump from `from` in the `heading` direction
+fn place_next(
+    rng: &mut LevelRng,
+    arc: &JumpArc,
+    settings: &GeneratorSettings,
+    from: &Surface,
+    size: Vec3,
+    heading: f32,
+) -> Surface {
+    let max_rise = arc.max_height() * arc.safety * settings.gap_usage;
+    let mut rise = rng.range(max_rise * 0.3, max_rise);
+
+    for _ in 0..PLACEMENT_ATTEMPTS {
+        let reach = arc.max_reach(rise).unwrap_or(0.0) * arc.safety * settings.gap_usage;
+        let gap = rng.range(MIN_GAP, reach.max(MIN_GAP));
+        let candidate = surface_beyond(from, size, heading, gap, rise);
+        if can_jump(arc, from, &candidate) {
+            return candidate;
+        }
+        rise *= 0.7;
+    }
+
+    // A flat hop, or failing that a step flush against `from` that needs no real jump
+    let hop = surface_beyond(from, size, heading, MIN_GAP, 0.0);
+    if can_jump(arc, from, &hop) {
+        return hop;
+    }
+    surface_flush(from, size, heading)
+}
+
+// Box of `size` whose footprint starts `gap` past `from` along `heading`, top `rise` higher
+fn surface_beyond(from: &Surface, size: Vec3, heading: f32, gap: f32, rise: f32) -> Surface {
+    let direction = Vec3::new(heading.cos(), 0.0, heading.sin());
+    // Half-extent of an axis-aligned box along a direction (its support distance)
+    let extent = |size: Vec3| (direction.x.abs() * size.x + direction.z.abs() * size.z) / 2.0;
+    let offset = direction * (extent(from.size) + gap + extent(size));
+
+    let mut center = from.center + offset;
+    center.y = from.top() + rise - size.y / 2.0;
+    Surface { center, size }
+}
+
+// Box of `size` touching `from` on the side `heading` points to most, tops level
+fn surface_flush(from: &Surface, size: Vec3, heading: f32) -> Surface {
+    let (sin, cos) = heading.sin_cos();
+    let mut center = from.center;
+    if cos.abs() >= sin.abs() {
+        center.x += cos.signum() * (from.size.x + size.x) / 2.0;
+    } else {
+        center.z += sin.signum() * (from.size.z + size.z) / 2.0;
+    }
+    center.y = from.top() - size.y / 2.0;
+    Surface { center, size }
+}
+
+// Side trips: a platform shuttling out from the chain, returned with the far end of
+// each ride where its collectible goes
+fn generate_moving_platforms(
+    rng: &mut LevelRng,
+    arc: &JumpArc,
+    settings: &GeneratorSettings,
+    chain: &[Surface],
+) -> (Vec<MovingPlatformData>, Vec<Vec3>) {
+    let mut moving_platforms = Vec::new();
+    let mut far_ends = Vec::new();
+    if chain.len() < 3 {
+        return (moving_platforms, far_ends);
+    }
+
+    for _ in 0..settings.moving_platforms * PLACEMENT_ATTEMPTS as usize {
+        if moving_platforms.len() == settings.moving_platforms {
+            break;
+        }
+
+        let anchor = &chain[1 + rng.index(chain.len() - 2)];
+        let size = Vec3::new(settings.platform_size * 1.2, 0.5, settings.platform_size * 0.8);
+        let heading = rng.range(0.0, TAU);
+        let near = surface_beyond(anchor, size, heading, MIN_GAP + 0.5, 0.0);
+        let far = near.center + Vec3::new(heading.cos(), 0.0, heading.sin()) * rng.range(4.0, 8.0);
+
+        // Boarding is a flat hop, and the ride must not sweep through other platforms
+        let sweeps_chain = chain.iter().any(|surface| {
+            !std::ptr::eq(surface, anchor)
+                && (0..=8).any(|step| {
+                    let point = near.center.lerp(far, step as f32 / 8.0);
+                    (surface.center.y - point.y).abs() < 3.0
+                        && surface.distance_to_point(point) < size.x + 1.0
+                })
+        });
+        if !can_jump(arc, anchor, &near) || sweeps_chain {
+            continue;
+        }
+
+        let color = [1.0, 0.0, 1.0];
+        moving_platforms.push(MovingPlatformData {
+            start: near.center.to_array(),
+            end: far.to_array(),
+            size: size.to_array(),
+            speed: rng.range(2.0, 3.0 + settings.gap_usage * 4.0),
+            color,
+            emissive: Some([0.3, 0.0, 0.3]),
+            ..default()
+        });
+        far_ends.push(far);
+    }
+
+    (moving_platforms, far_ends)
+}
+
+// Spread along the chain, always one on the summit and one at each moving platform's far end
+fn place_collectibles(
+    level: u32,
+    settings: &GeneratorSettings,
+    chain: &[Surface],
+    far_ends: &[Vec3],
+) -> Vec<CollectibleData> {
+    let mut positions: Vec<Vec3> = far_ends.iter().map(|far| *far + Vec3::Y * 1.25).collect();
+
+    let on_chain = settings.collectibles.saturating_sub(positions.len()).max(1);
+    let step = (chain.len() as f32 / on_chain as f32).max(1.0);
+    for i in 0..on_chain {
+        let index = (chain.len() - 1).saturating_sub((i as f32 * step) as usize);
+        if let Some(surface) = chain.get(index) {
+            positions.push(Vec3::new(surface.center.x, surface.top() + 1.0, surface.center.z));
+        }
+    }
+
+    positions
+        .into_iter()
+        .map(|position| {
+            let color = if position.y > 50.0 { [1.0, 0.8, 0.0] } else { [1.0, 1.0, 0.0] };
+            CollectibleData {
+                position: position.to_array(),
+                value: 10 * level + (position.y / 10.0) as u32, // Higher pickups are worth more
+                color,
+                emissive: Some(color.map(|channel| channel * 0.3)),
+            }
+        })
+        .collect()
+}
+
+// Hazards on the ground and floating beside the route, never on it
+fn place_hazards(
+    rng: &mut LevelRng,
+    settings: &GeneratorSettings,
+    chain: &[Surface],
+    moving_platforms: &[MovingPlatformData],
+) -> Vec<HazardData> {
+    let spawn = Vec3::new(0.0, 2.0, 0.0);
+    let mut hazards = Vec::new();
+    if chain.is_empty() {
+        return hazards;
+    }
+
+    for attempt in 0..settings.hazards * PLACEMENT_ATTEMPTS as usize {
+        if hazards.len() == settings.hazards {
+            break;
+        }
+
+        let position = if attempt % 2 == 0 {
+            let angle = rng.range(0.0, TAU);
+            Vec3::new(angle.cos(), 0.0, angle.sin()) * rng.range(4.0, GROUND_SIZE[0] / 2.0 - 1.0) + Vec3::Y
+        } else {
+            let anchor = &chain[rng.index(chain.len())];
+            let angle = rng.range(0.0, TAU);
+            anchor.center + Vec3::new(angle.cos(), 0.0, angle.sin()) * rng.range(4.0, 8.0) + Vec3::Y * rng.range(0.5, 2.0)
+        };
+
+        if is_clear_of_route(position, spawn, chain, moving_platforms) {
+            let color = [1.0, 0.2, 0.2];
+            hazards.push(HazardData {
+                position: position.to_array(),
+                size: [HAZARD_SIZE; 3],
+                color,
+                emissive: Some([0.3, 0.0, 0.0]),
+            });
+        }
+    }
+
+    hazards
+}
+
+fn is_clear_of_route(position: Vec3, spawn: Vec3, chain: &[Surface], moving_platforms: &[MovingPlatformData]) -> bool {
+    let near_height = |y: f32| (position.y - y).abs() < HAZARD_CLEARANCE + 2.0;
+    let flat = |point: Vec3| Vec2::new(point.x, point.z);
+
+    if flat(position).distance(flat(spawn)) < HAZARD_CLEARANCE + 1.0 {
+        return false;
+    }
+    if chain
+        .iter()
+        .any(|surface| near_height(surface.top()) && surface.distance_to_point(position) < HAZARD_CLEARANCE)
+    {
+        return false;
+    }
+
+    // Jumps roughly follow the straight line between consecutive tops
+    let ground = Surface::new(GROUND_CENTER, GROUND_SIZE);
+    let mut previous = Vec3::new(ground.center.x, ground.top(), ground.center.z);
+    for surface in chain {
+        let top = Vec3::new(surface.center.x, surface.top(), surface.center.z);
+        for step in 0..=8 {
+            let point = previous.lerp(top, step as f32 / 8.0);
+            if near_height(point.y) && flat(point).distance(flat(position)) < HAZARD_CLEARANCE {
+                return false;
+            }
+        }
+        previous = top;
+    }
+
+    moving_platforms.iter().all(|platform| {
+        (0..=8).all(|step| {
+            let point = Vec3::from(platform.start).lerp(Vec3::from(platform.end), step as f32 / 8.0);
+            !near_height(point.y) || flat(point).distance(flat(position)) >= HAZARD_CLEARANCE + platform.size[0]
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::{apply_difficulty, Difficulty};
+    use crate::reachability::analyze_level;
+
+    const SEEDS: [u64; 4] = [0, 1, 42, 0xDEAD_BEEF];
+
+    #[test]
+    fn level_rng_is_deterministic() {
+        let mut first = LevelRng::new(42);
+        let mut second = LevelRng::new(42);
+        let mut other = LevelRng::new(43);
+        let draws: Vec<u64> = (0..16).map(|_| first.next_u64()).collect();
+        assert_eq!(draws, (0..16).map(|_| second.next_u64()).collect::<Vec<_>>());
+        assert_ne!(draws, (0..16).map(|_| other.next_u64()).collect::<Vec<_>>());
+
+        for _ in 0..1000 {
+            let value = first.range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&value));
+            assert!(first.index(7) < 7);
+        }
+    }
+
+    #[test]
+    fn same_seed_builds_the_same_level() {
+        let arc = JumpArc::default();
+        let build = |seed| ron::to_string(&generate_level(8, seed, &arc)).unwrap();
+        assert_eq!(build(level_seed(42, 8)), build(level_seed(42, 8)));
+        assert_ne!(build(level_seed(42, 8)), build(level_seed(43, 8)));
+        assert_ne!(level_seed(42, 8), level_seed(42, 9));
+    }
+
+    #[test]
+    fn every_chain_gap_fits_the_arc() {
+        // The default player, and one too weak to clear even the shortest hop
+        let weak = JumpArc {
+            speed: 1.0,
+            jump_force: 0.1,
+            ..default()
+        };
+        for arc in [JumpArc::default(), weak] {
+            for level in [1, 6, 12, 30] {
+                for seed in SEEDS {
+                    let settings = GeneratorSettings::for_level(level);
+                    let chain = generate_chain(&mut LevelRng::new(seed), &arc, &settings);
+                    let ground = Surface::new(GROUND_CENTER, GROUND_SIZE);
+                    for (from, to) in std::iter::once(&ground).chain(&chain).zip(&chain) {
+                        assert!(can_jump(&arc, from, to), "level {} seed {}: {:?} -> {:?}", level, seed, from, to);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_levels_are_fully_reachable() {
+        let arc = JumpArc::default();
+        for difficulty in Difficulty::ALL {
+            for level in [1, 6, 12, 30] {
+                for seed in SEEDS {
+                    let mut data = generate_level(level, level_seed(seed, level), &arc);
+                    apply_difficulty(difficulty, level, &mut data, true);
+                    let report = analyze_level(&data, &arc);
+                    assert!(
+                        report.standables.iter().all(|standable| standable.reachable),
+                        "{:?} level {} seed {}",
+                        difficulty,
+                        level,
+                        seed
+                    );
+                    assert_eq!(report.unreachable_collectibles().count(), 0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::editor::not_play_testing;
+use crate::events::GhostSaved;
+use crate::level::ProceduralLevels;
+use crate::save::{record_level_complete, ProfilePath, RunClock};
+use crate::{player_model, AppState, GameState, Player};
+
+// Trajectory samples per second; the ghost interpolates in between
+pub const GHOST_SAMPLE_RATE: f32 = 20.0;
+pub const GHOST_ALPHA: f32 = 0.35;
+
+// ===== GHOST PLUGIN =====
+// Records the player's path through each level and replays the fastest clear
+// as a translucent ghost racing alongside the live player.
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BestGhosts>()
+            .init_resource::<GhostRecorder>()
+            .add_systems(Startup, load_ghosts)
+            .add_systems(
+                Update,
+                (record_trajectory, sync_ghost).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(
+                OnEnter(AppState::LevelTransition),
+                save_best_ghost
+                    .run_if(not_play_testing)
+                    .before(record_level_complete), // Needs the level clock before it resets
+            );
+    }
+}
+
+// ===== GHOST DATA =====
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct GhostSample {
+    pub time: f32,
+    pub position: [f32; 3],
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct GhostRun {
+    pub time_seconds: f32, // Clear time for the level
+    pub samples: Vec<GhostSample>,
+}
+
+impl GhostRun {
+    // Position at `time` into the level, holding the last sample once the run is over
+    pub fn position_at(&self, time: f32) -> Option<Vec3> {
+        let next = self.samples.partition_point(|sample| sample.time <= time);
+        let after = self.samples.get(next);
+        let before = next.checked_sub(1).and_then(|i| self.samples.get(i));
+
+        match (before, after) {
+            (Some(a), Some(b)) => {
+                let t = (time - a.time) / (b.time - a.time).max(f32::EPSILON);
+                Some(Vec3::from(a.position).lerp(Vec3::from(b.position), t))
+            }
+            (Some(sample), None) | (None, Some(sample)) => Some(Vec3::from(sample.position)),
+            (None, None) => None,
+        }
+    }
+}
+
+// Fastest recorded clear per difficulty and level number. Only the shipped
+// level files have ghosts: a generated level changes with the seed.
+#[derive(Resource, Default)]
+pub struct BestGhosts {
+    pub runs: BTreeMap<(Difficulty, u32), GhostRun>,
+}
+
+// The attempt in progress on the current level
+#[derive(Resource, Default)]
+pub struct GhostRecorder {
+    pub difficulty: Difficulty,
+    pub level: u32,
+    pub run: GhostRun,
+}
+
+#[derive(Component)]
+pub struct Ghost {
+    pub difficulty: Difficulty,
+    pub level: u32,
+}
+
+// Ghosts live next to the profile: `.../extreme-3d-platformer/ghosts/level_N.ghost.ron`
+// for Extreme, which keeps ghosts saved before presets existed, and
+// `level_N.<difficulty>.ghost.ron` for the others
+fn ghost_dir(profile_path: &ProfilePath) -> Option<PathBuf> {
+    Some(profile_path.0.as_ref()?.parent()?.join("ghosts"))
+}
+
+fn ghost_file_name(difficulty: Difficulty, level: u32) -> String {
+    match difficulty {
+        Difficulty::Extreme => format!("level_{}.ghost.ron", level),
+        _ => format!("level_{}.{}.ghost.ron", level, difficulty.name().to_lowercase()),
+    }
+}
+
+fn parse_ghost_file_name(name: &str) -> Option<(Difficulty, u32)> {
+    let stem = name.strip_prefix("level_")?.strip_suffix(".ghost.ron")?;
+    match stem.split_once('.') {
+        Some((level, difficulty)) => Some((Difficulty::from_name(difficulty)?, level.parse().ok()?)),
+        None => Some((Difficulty::Extreme, stem.parse().ok()?)),
+    }
+}
+
+fn ghost_path(profile_path: &ProfilePath, difficulty: Difficulty, level: u32) -> Option<PathBuf> {
+    Some(ghost_dir(profile_path)?.join(ghost_file_name(difficulty, level)))
+}
+
+// ===== PERSISTENCE =====
+fn load_ghosts(profile_path: Res<ProfilePath>, mut ghosts: ResMut<BestGhosts>) {
+    let Some(dir) = ghost_dir(&profile_path) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        let key = path.file_name().and_then(|name| name.to_str()).and_then(parse_ghost_file_name);
+        let Some(key) = key else {
+            continue;
+        };
+        match fs::read_to_string(&path).map(|contents| ron::from_str::<GhostRun>(&contents)) {
+            Ok(Ok(run)) => {
+                ghosts.runs.insert(key, run);
+            }
+            Ok(Err(err)) => warn!("Ignoring unreadable ghost {}: {}", path.display(), err),
+            Err(err) => warn!("Could not read ghost {}: {}", path.display(), err),
+        }
+    }
+
+    if !ghosts.runs.is_empty() {
+        info!("👻 Loaded ghosts for {} levels", ghosts.runs.len());
+    }
+}
+
+fn save_ghost(profile_path: &ProfilePath, difficulty: Difficulty, level: u32, run: &GhostRun) {
+    let Some(path) = ghost_path(profile_path, difficulty, level) else {
+        return;
+    };
+    let contents = match ron::ser::to_string(run) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Could not serialize ghost: {}", err);
+            return;
+        }
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create ghost directory {}: {}", dir.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = fs::write(&path, contents) {
+        warn!("Could not write ghost {}: {}", path.display(), err);
+    }
+}
+
+// ===== RECORDING SYSTEMS =====
+fn record_trajectory(
+    game_state: Res<GameState>,
+    difficulty: Res<Difficulty>,
+    clock: Res<RunClock>,
+    mut recorder: ResMut<GhostRecorder>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    // A new level or a reset clock (restart) starts a fresh attempt
+    let last_time = recorder.run.samples.last().map(|sample| sample.time);
+    if recorder.level != game_state.level
+        || recorder.difficulty != *difficulty
+        || last_time.is_some_and(|time| clock.level_seconds < time)
+    {
+        recorder.difficulty = *difficulty;
+        recorder.level = game_state.level;
+        recorder.run = GhostRun::default();
+    }
+
+    let due = last_time.map_or(true, |time| clock.level_seconds - time >= 1.0 / GHOST_SAMPLE_RATE);
+    if let (true, Ok(transform)) = (due, player_query.get_single()) {
+        recorder.run.samples.push(GhostSample {
+            time: clock.level_seconds,
+            position: transform.translation.to_array(),
+        });
+    }
+}
+
+fn save_best_ghost(
+    game_state: Res<GameState>,
+    clock: Res<RunClock>,
+    profile_path: Res<ProfilePath>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut ghosts: ResMut<BestGhosts>,
+    mut ghost_events: EventWriter<GhostSaved>,
+    procedural: Res<ProceduralLevels>,
+) {
+    let completed_level = game_state.level - 1;
+    if recorder.level != completed_level || recorder.run.samples.is_empty() {
+        return;
+    }
+    if procedural.is_generated(completed_level) {
+        recorder.run = GhostRun::default();
+        return;
+    }
+
+    let key = (recorder.difficulty, completed_level);
+    let is_best = ghosts
+        .runs
+        .get(&key)
+        .map_or(true, |best| clock.level_seconds < best.time_seconds);
+    let mut run = std::mem::take(&mut recorder.run);
+    if is_best {
+        run.time_seconds = clock.level_seconds;
+        save_ghost(&profile_path, key.0, completed_level, &run);
+        ghosts.runs.insert(key, run);
+        ghost_events.send(GhostSaved {
+            difficulty: key.0,
+            level: completed_level,
+        });
+    }
+}
+
+// ===== GHOST PLAYBACK =====
+fn sync_ghost(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    game_state: Res<GameState>,
+    difficulty: Res<Difficulty>,
+    clock: Res<RunClock>,
+    ghosts: Res<BestGhosts>,
+    procedural: Res<ProceduralLevels>,
+    mut ghost_query: Query<(Entity, &Ghost, &mut Transform)>,
+) {
+    let run = ghosts
+        .runs
+        .get(&(*difficulty, game_state.level))
+        .filter(|_| !procedural.is_generated(game_state.level));
+
+    let mut has_ghost = false;
+    for (entity, ghost, mut transform) in &mut ghost_query {
+        match run.and_then(|run| run.position_at(clock.level_seconds)) {
+            Some(position) if ghost.level == game_state.level && ghost.difficulty == *difficulty => {
+                transform.translation = position;
+                has_ghost = true;
+            }
+            _ => commands.entity(entity).despawn_recursive(),
+        }
+    }
+
+    let Some(position) = run.and_then(|run| run.position_at(clock.level_seconds)) else {
+        return;
+    };
+    if has_ghost {
+        return;
+    }
+
+    // Same model as the player, translucent and without physics or animation markers
+    let [head, rest @ ..] = player_model().map(|mut part| {
+        part.color = part.color.with_a(GHOST_ALPHA);
+        part
+    });
+    commands
+        .spawn((
+            PbrBundle {
+                transform: Transform::from_translation(position),
+                ..head.bundle(&mut meshes, &mut materials)
+            },
+            Ghost {
+                difficulty: *difficulty,
+                level: game_state.level,
+            },
+        ))
+        .with_children(|parent| {
+            for part in rest {
+                parent.spawn(part.bundle(&mut meshes, &mut materials));
+            }
+        });
+}
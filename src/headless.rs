@@ -0,0 +1,100 @@
+use bevy::app::AppExit;
+use bevy::core::FrameCount;
+use bevy::input::{InputPlugin, InputSystem};
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::controls::ActionSystem;
+use crate::save::ProfilePath;
+use crate::{physics_timestep, AppState};
+
+// ===== HEADLESS PLUGIN =====
+// Window-less replacement for `DefaultPlugins`: just enough engine for
+// `GamePlugin` and Rapier to run on a GPU-less machine.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            LogPlugin::default(), // Where `GameLogPlugin` and warnings go
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            AssetPlugin::default(),
+            ScenePlugin, // Rapier's async scene colliders expect a `SceneSpawner`
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .add_event::<ReceivedCharacter>() // Read by the options screen's name entry
+        .insert_resource(TimeUpdateStrategy::ManualDuration(physics_timestep())) // One physics step per update
+        .insert_resource(NextState(Some(AppState::Playing))) // No one to press Enter on the title menu
+        .insert_resource(ProfilePath(None)) // Never touch the player's real save file
+        .init_resource::<ScriptedInput>()
+        .add_systems(PreUpdate, apply_scripted_input.after(InputSystem).before(ActionSystem))
+        .add_systems(Last, exit_after_frame_limit);
+    }
+}
+
+// ===== SCRIPTED INPUT =====
+#[derive(Clone, Copy, Debug)]
+pub struct ScriptedKey {
+    pub frame: u32,
+    pub key: KeyCode,
+    pub pressed: bool,
+}
+
+// Key presses and releases replayed into `Input<KeyCode>` on given frames
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ScriptedInput {
+    pub keys: Vec<ScriptedKey>,
+}
+
+impl ScriptedInput {
+    pub fn press(mut self, frame: u32, key: KeyCode) -> Self {
+        self.keys.push(ScriptedKey { frame, key, pressed: true });
+        self
+    }
+
+    pub fn release(mut self, frame: u32, key: KeyCode) -> Self {
+        self.keys.push(ScriptedKey { frame, key, pressed: false });
+        self
+    }
+
+    // Hold `key` from frame `start` until it is released on frame `end`
+    pub fn hold(self, key: KeyCode, start: u32, end: u32) -> Self {
+        self.press(start, key).release(end, key)
+    }
+}
+
+fn apply_scripted_input(
+    script: Res<ScriptedInput>,
+    frame_count: Res<FrameCount>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+) {
+    for scripted in script.keys.iter().filter(|scripted| scripted.frame == frame_count.0) {
+        if scripted.pressed {
+            keyboard_input.press(scripted.key);
+        } else {
+            keyboard_input.release(scripted.key);
+        }
+    }
+}
+
+// ===== FRAME LIMIT =====
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct FrameLimit(pub u32);
+
+fn exit_after_frame_limit(
+    limit: Option<Res<FrameLimit>>,
+    frame_count: Res<FrameCount>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Some(limit) = limit {
+        if frame_count.0 >= limit.0 {
+            exit.send(AppExit);
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use std::path::PathBuf;
+
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::utils::BoxedFuture;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::platform::{CrumbleState, Easing, MovingPlatform, OneWayPlatform, PathMode, PlatformKind};
+use crate::powerup::{PowerUp, PowerUpKind, POWER_UP_RADIUS};
+use crate::{Checkpoint, Collectible, Hazard, LevelEntity, COLLECTIBLE_RADIUS};
+
+// Levels past the last shipped file are generated (endless mode)
+pub const LEVEL_FILE_COUNT: u32 = 5;
+
+pub fn level_path(level: u32) -> String {
+    format!("levels/level_{}.level.ron", level.clamp(1, LEVEL_FILE_COUNT))
+}
+
+// The `assets` directory the asset server reads at runtime: under `BEVY_ASSET_ROOT`,
+// `CARGO_MANIFEST_DIR` (set by `cargo run`) or next to the executable
+pub fn asset_dir() -> PathBuf {
+    FileAssetReader::get_base_path().join("assets")
+}
+
+// Source file of a shipped level, for tools that read or save it directly
+pub fn level_file(level: u32) -> PathBuf {
+    asset_dir().join(level_path(level))
+}
+
+// ===== LEVEL DATA =====
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct LevelData {
+    pub name: String,
+    pub spawn_point: [f32; 3],
+    pub lighting: LightingData,
+    pub platforms: Vec<PlatformData>,
+    pub moving_platforms: Vec<MovingPlatformData>,
+    pub collectibles: Vec<CollectibleData>,
+    pub hazards: Vec<HazardData>,
+    #[serde(default)]
+    pub checkpoints: Vec<CheckpointData>,
+    #[serde(default)]
+    pub power_ups: Vec<PowerUpData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightingData {
+    pub sun_illuminance: f32,
+    pub sun_position: [f32; 3],
+    pub point_lights: Vec<PointLightData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointLightData {
+    pub position: [f32; 3],
+    pub intensity: f32,
+    pub range: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformData {
+    pub position: [f32; 3],
+    pub size: [f32; 3],
+    pub color: [f32; 3],
+    #[serde(default)]
+    pub emissive: Option<[f32; 3]>,
+    #[serde(default)]
+    pub kind: PlatformKind,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MovingPlatformData {
+    pub start: [f32; 3],
+    pub end: [f32; 3],
+    pub size: [f32; 3],
+    pub speed: f32,
+    pub color: [f32; 3],
+    #[serde(default)]
+    pub emissive: Option<[f32; 3]>,
+    #[serde(default)]
+    pub waypoints: Vec<[f32; 3]>, // Visited between `start` and `end`
+    #[serde(default)]
+    pub mode: PathMode,
+    #[serde(default)]
+    pub easing: Easing,
+    #[serde(default)]
+    pub pause: f32, // Seconds at each end
+    #[serde(default)]
+    pub rotate: [f32; 3], // Degrees about X, Y, Z reached at the end of the path
+    #[serde(default)]
+    pub spin: [f32; 3], // Degrees per second about X, Y, Z
+    #[serde(default)]
+    pub start_on_step: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectibleData {
+    pub position: [f32; 3],
+    pub value: u32,
+    pub color: [f32; 3],
+    #[serde(default)]
+    pub emissive: Option<[f32; 3]>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HazardData {
+    pub position: [f32; 3],
+    pub size: [f32; 3],
+    pub color: [f32; 3],
+    #[serde(default)]
+    pub emissive: Option<[f32; 3]>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointData {
+    pub position: [f32; 3], // Base of the marker, usually a platform top
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerUpData {
+    pub position: [f32; 3],
+    pub kind: PowerUpKind,
+    #[serde(default)]
+    pub duration: Option<f32>, // Seconds; the kind's default when left out
+}
+
+// ===== LEVEL LOADER =====
+#[derive(Default)]
+pub struct LevelLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum LevelLoaderError {
+    #[error("Could not read level file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse level file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for LevelLoader {
+    type Asset = LevelData;
+    type Settings = ();
+    type Error = LevelLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let level = ron::de::from_bytes::<LevelData>(&bytes)?;
+            Ok(level)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+// ===== LEVEL RESOURCES =====
+#[derive(Resource, Default)]
+pub struct LevelLibrary {
+    pub handles: Vec<Handle<LevelData>>,
+}
+
+impl LevelLibrary {
+    pub fn get(&self, level: u32) -> Option<&Handle<LevelData>> {
+        self.handles.get((level.clamp(1, LEVEL_FILE_COUNT) - 1) as usize)
+    }
+}
+
+#[derive(Resource)]
+pub struct ActiveLevel {
+    pub pending: Option<u32>, // Level waiting for its file to finish loading
+    pub spawn_point: Vec3,
+    pub keep_player: bool, // Rebuild the pending level around the player instead of respawning them
+}
+
+impl Default for ActiveLevel {
+    fn default() -> Self {
+        Self {
+            pending: Some(1),
+            spawn_point: Vec3::new(0.0, 2.0, 0.0),
+            keep_player: false,
+        }
+    }
+}
+
+// First level built by `generator::generate_level` instead of read from a file
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ProceduralLevels {
+    pub from_level: u32,
+}
+
+impl Default for ProceduralLevels {
+    fn default() -> Self {
+        Self {
+            from_level: LEVEL_FILE_COUNT + 1,
+        }
+    }
+}
+
+impl ProceduralLevels {
+    pub fn is_generated(&self, level: u32) -> bool {
+        level >= self.from_level
+    }
+}
+
+fn color(rgb: [f32; 3]) -> Color {
+    Color::rgb(rgb[0], rgb[1], rgb[2])
+}
+
+fn material(
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    base: [f32; 3],
+    emissive: Option<[f32; 3]>,
+) -> Handle<StandardMaterial> {
+    match emissive {
+        Some(emissive) => materials.add(StandardMaterial {
+            base_color: color(base),
+            emissive: color(emissive), // Glowing effect
+            ..default()
+        }),
+        None => materials.add(color(base).into()),
+    }
+}
+
+// ===== LEVEL SPAWNING =====
+pub fn spawn_level_content(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    level: u32,
+    data: &LevelData,
+) {
+    info!("🔥 Spawning EXTREME Level {} ({})", level, data.name);
+
+    // Lighting
+    commands.spawn((
+        DirectionalLightBundle {
+            directional_light: DirectionalLight {
+                illuminance: data.lighting.sun_illuminance,
+                shadows_enabled: true,
+                ..default()
+            },
+            transform: Transform {
+                translation: Vec3::from(data.lighting.sun_position),
+                rotation: Quat::from_rotation_x(-std::f32::consts::FRAC_PI_4),
+                ..default()
+            },
+            ..default()
+        },
+        LevelEntity,
+    ));
+
+    for light in &data.lighting.point_lights {
+        commands.spawn((
+            PointLightBundle {
+                point_light: PointLight {
+                    intensity: light.intensity,
+                    range: light.range,
+                    shadows_enabled: false,
+                    ..default()
+                },
+                transform: Transform::from_translation(Vec3::from(light.position)),
+                ..default()
+            },
+            LevelEntity,
+        ));
+    }
+
+    // Static platforms
+    for (i, platform) in data.platforms.iter().enumerate() {
+        let size = Vec3::from(platform.size);
+        let position = Vec3::from(platform.position);
+
+        let transform = Transform::from_translation(position);
+        let mut entity = commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: material(materials, platform.color, platform.emissive),
+                transform,
+                ..default()
+            },
+            RigidBody::Fixed,
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            platform.kind,
+            LevelEntity,
+        ));
+
+        match platform.kind {
+            PlatformKind::Crumbling { .. } => {
+                entity.insert((CrumbleState::new(transform), Velocity::zero()));
+            }
+            PlatformKind::Bouncy { restitution } => {
+                entity.insert(Restitution {
+                    coefficient: restitution,
+                    combine_rule: CoefficientCombineRule::Max, // The player's own 0.1 mustn't dampen it
+                });
+            }
+            PlatformKind::Ice { friction } => {
+                entity.insert(Friction {
+                    coefficient: friction,
+                    combine_rule: CoefficientCombineRule::Min,
+                });
+            }
+            PlatformKind::OneWay => {
+                entity.insert(OneWayPlatform { top: position.y + size.y / 2.0 });
+            }
+            PlatformKind::Solid | PlatformKind::Conveyor { .. } => {}
+        }
+
+        // Debug print for extreme platforms
+        if size.x < 1.0 {
+            debug!("⚠️  Extreme platform {}: Size {:.2}x{:.2} at height {:.1}",
+                   i + 1, size.x, size.z, position.y);
+        }
+    }
+
+    // Moving platforms
+    for platform in &data.moving_platforms {
+        let size = Vec3::from(platform.size);
+        let start_pos = Vec3::from(platform.start);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: material(materials, platform.color, platform.emissive),
+                transform: Transform::from_translation(start_pos),
+                ..default()
+            },
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            Ccd::enabled(),
+            TransformInterpolation::default(),
+            MovingPlatform::from_data(platform),
+            LevelEntity,
+        ));
+    }
+
+    // Collectibles
+    for collectible in &data.collectibles {
+        let position = Vec3::from(collectible.position);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::UVSphere {
+                    radius: COLLECTIBLE_RADIUS,
+                    sectors: 12,
+                    stacks: 12,
+                })),
+                material: material(materials, collectible.color, collectible.emissive),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            RigidBody::Fixed,
+            Collider::ball(COLLECTIBLE_RADIUS),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Collectible { value: collectible.value },
+            LevelEntity,
+        ));
+
+        // Debug info for extreme collectibles
+        if position.y > 100.0 {
+            debug!("💎 EXTREME Collectible at height {:.1} - Value: {} points",
+                   position.y, collectible.value);
+        }
+    }
+
+    // Hazards
+    for hazard in &data.hazards {
+        let size = Vec3::from(hazard.size);
+        let position = Vec3::from(hazard.position);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: material(materials, hazard.color, hazard.emissive),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            RigidBody::Fixed,
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Hazard { half_extents: size / 2.0 },
+            LevelEntity,
+        ));
+
+        // Debug info for elevated hazards
+        if position.y > 50.0 {
+            debug!("⚠️  EXTREME HAZARD at height {:.1}", position.y);
+        }
+    }
+
+    // Checkpoints
+    for checkpoint in &data.checkpoints {
+        let position = Vec3::from(checkpoint.position);
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Cylinder {
+                    radius: 0.1,
+                    height: 2.0,
+                    resolution: 8,
+                    segments: 1,
+                })),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgb(0.6, 0.6, 0.6),
+                    emissive: Color::rgb(0.1, 0.1, 0.1),
+                    ..default()
+                }),
+                transform: Transform::from_translation(position + Vec3::Y), // Pole stands on its base
+                ..default()
+            },
+            Checkpoint {
+                respawn_point: position + Vec3::Y * 1.5,
+                activated: false,
+            },
+            LevelEntity,
+        ));
+    }
+
+    // Power-ups
+    for power_up in &data.power_ups {
+        let color = power_up.kind.color();
+
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Cube { size: POWER_UP_RADIUS * 1.4 })),
+                material: materials.add(StandardMaterial {
+                    base_color: color,
+                    emissive: color * 0.5,
+                    ..default()
+                }),
+                transform: Transform::from_translation(Vec3::from(power_up.position))
+                    .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_4)),
+                ..default()
+            },
+            RigidBody::Fixed,
+            Collider::ball(POWER_UP_RADIUS),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            PowerUp {
+                kind: power_up.kind,
+                duration: power_up.duration.unwrap_or_else(|| power_up.kind.default_duration()),
+            },
+            LevelEntity,
+        ));
+    }
+
+    info!("💀 Level {} spawned with {} hazards ({} elevated)",
+          level, data.hazards.len(), data.hazards.iter().filter(|h| h.position[1] > 5.0).count());
+}
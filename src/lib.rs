@@ -0,0 +1,1169 @@
+﻿use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub mod audio;
+pub mod camera;
+pub mod controls;
+pub mod difficulty;
+pub mod editor;
+pub mod events;
+pub mod game_log;
+pub mod ghost;
+pub mod generator;
+pub mod headless;
+pub mod level;
+pub mod menu;
+pub mod platform;
+pub mod powerup;
+pub mod reachability;
+pub mod replay;
+pub mod save;
+pub mod scoring;
+pub mod settings;
+pub mod speedrun;
+pub mod tuning;
+
+use audio::SoundEvent;
+use camera::{CameraPlugin, OrbitCamera};
+use controls::{Action, ActionAxes, ActionSystem, ControlsPlugin};
+use difficulty::Difficulty;
+use editor::LevelEditor;
+use events::{
+    CheckpointReached, GameOver, GameReset, GhostSaved, ItemCollected, LeaderboardRank, LevelCompleted, NewBestTime,
+    PersonalBest, PlayerDamaged, PlayerFell, PlayerJumped, PlayerLanded, PowerUpCollected, RunRecordEvents,
+};
+use ghost::GhostPlugin;
+use level::{ActiveLevel, LevelData, LevelLibrary, LevelLoader, ProceduralLevels};
+use reachability::JumpArc;
+use menu::MenuPlugin;
+use powerup::{ActiveEffects, PowerUpKind, PowerUpPlugin, SHIELD_INVULNERABILITY};
+use platform::{crumble_platforms, move_platforms, update_one_way_platforms, MovingPlatform, PlatformKind, ICE_CONTROL};
+use save::{Profile, ProfilePath, RunClock, SavePlugin};
+use scoring::{LevelScore, ScoreBreakdown, ScoringPlugin, ScoringRules};
+use settings::SettingsPlugin;
+use speedrun::{RunSplits, SpeedrunPlugin};
+use tuning::{Tuning, TuningPlugin};
+
+// ===== GAME PLUGIN =====
+// All gameplay systems plus Rapier; needs asset, input and transform plugins from
+// the app (windowed or `HeadlessPlugin`).
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        // Physics and the systems that drive it step together in `FixedUpdate`
+        app.insert_resource(RapierConfiguration {
+            timestep_mode: TimestepMode::Fixed {
+                dt: PHYSICS_TIMESTEP,
+                substeps: 1,
+            },
+            ..default()
+        })
+        .insert_resource(Time::<Fixed>::from_duration(physics_timestep()))
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule());
+
+        app.insert_resource(GameState {
+            score: 0,
+            lives: 3,
+            level: 1,
+            collectibles_in_level: 8, // More collectibles for difficulty
+        })
+        .init_asset::<LevelData>()
+        .init_asset_loader::<LevelLoader>()
+        .init_resource::<LevelLibrary>()
+        .init_resource::<ActiveLevel>()
+        .init_resource::<ProceduralLevels>()
+        .init_resource::<JumpSettings>()
+        .init_resource::<ActiveCheckpoint>()
+        .init_resource::<RunSeed>()
+        .init_resource::<Difficulty>()
+        .add_state::<AppState>()
+        .add_event::<RestartRequested>()
+        .add_event::<SoundEvent>()
+        .add_event::<PlayerJumped>()
+        .add_event::<PlayerLanded>()
+        .add_event::<ItemCollected>()
+        .add_event::<PlayerDamaged>()
+        .add_event::<PlayerFell>()
+        .add_event::<LevelCompleted>()
+        .add_event::<GameReset>()
+        .add_event::<GameOver>()
+        .add_event::<CheckpointReached>()
+        .add_event::<PowerUpCollected>()
+        .add_event::<NewBestTime>()
+        .add_event::<LeaderboardRank>()
+        .add_event::<PersonalBest>()
+        .add_event::<GhostSaved>()
+        .add_plugins((
+            MenuPlugin,
+            SavePlugin,
+            GhostPlugin,
+            PowerUpPlugin,
+            CameraPlugin,
+            ControlsPlugin,
+            SettingsPlugin,
+            SpeedrunPlugin,
+            TuningPlugin,
+            ScoringPlugin,
+        ))
+        .add_systems(Startup, (setup_game, load_levels))
+        .add_systems(
+            PreUpdate,
+            buffer_jump_press.after(ActionSystem).run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                player_movement,
+                detect_ground.before(player_movement).before(player_jump),
+                player_jump,
+                check_hazards,
+                move_platforms,
+                crumble_platforms,
+                update_one_way_platforms,
+            )
+                .before(PhysicsSet::SyncBackend)
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (
+                collect_items,
+                activate_checkpoints,
+                respawn_player,
+                animate_player,
+                check_level_complete.before(spawn_pending_level),
+                check_game_over,
+            )
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (
+                update_ui,
+                reset_game,
+                spawn_pending_level,
+                reload_modified_level.before(spawn_pending_level),
+                audio::send_gameplay_sounds,
+                difficulty::apply_difficulty_change
+                    .run_if(resource_changed::<Difficulty>())
+                    .before(spawn_pending_level),
+                pause_physics.run_if(state_changed::<AppState>()),
+            ),
+        );
+    }
+}
+
+// ===== STATES AND EVENTS =====
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Playing,
+    Paused,
+    LevelTransition,
+    GameOver,
+    Leaderboard,
+    Options,
+    Editor,
+}
+
+// Ask `reset_game` to start a fresh run without going through the R key
+#[derive(Event)]
+pub struct RestartRequested;
+
+// ===== COMPONENTS =====
+#[derive(Component)]
+pub struct Player {
+    pub speed: f32,
+    pub jump_force: f32,
+    pub is_grounded: bool,
+    pub coyote_timer: f32,      // Time left to jump after leaving the ground
+    pub jump_buffer_timer: f32, // Time left for an early jump press to still count
+    pub jump_pressed: bool,     // A jump press no physics step has seen yet
+    pub invulnerable_timer: f32,
+    pub animation_timer: f32,
+    pub ground_entity: Option<Entity>, // What the player is standing on, if anything
+    pub ground_velocity: Vec3,         // Velocity of the platform underfoot, carried over to movement
+    pub ground_kind: PlatformKind,
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+#[derive(Component)]
+pub struct Collectible {
+    pub value: u32,
+}
+
+#[derive(Component)]
+pub struct Hazard {
+    pub half_extents: Vec3, // Of the visible box; the hitbox is scaled by `Tuning::hazard_hitbox_scale`
+}
+
+#[derive(Component)]
+pub struct Checkpoint {
+    pub respawn_point: Vec3,
+    pub activated: bool,
+}
+
+#[derive(Component)]
+pub struct SpawnPoint;
+
+#[derive(Component)]
+pub struct GameUI;
+
+#[derive(Component)]
+pub struct LeftArm;
+
+#[derive(Component)]
+pub struct RightArm;
+
+#[derive(Component)]
+pub struct LeftLeg;
+
+#[derive(Component)]
+pub struct RightLeg;
+
+#[derive(Component)]
+pub struct LevelEntity; // Tag for level-specific entities that should be cleaned up
+
+pub const PLAYER_RADIUS: f32 = 0.4;
+pub const COLLECTIBLE_RADIUS: f32 = 0.5; // Larger for better visibility at extreme heights
+pub const COLLECT_RADIUS: f32 = COLLECTIBLE_RADIUS + PLAYER_RADIUS; // Center distance at which the spheres touch
+
+// ===== RESOURCES =====
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct JumpSettings {
+    pub coyote_time: f32,
+    pub jump_buffer: f32,
+    pub ground_check_distance: f32, // How far below the player's collider still counts as ground
+}
+
+impl Default for JumpSettings {
+    fn default() -> Self {
+        Self {
+            coyote_time: 0.12,
+            jump_buffer: 0.12,
+            ground_check_distance: 0.1,
+        }
+    }
+}
+
+// Last checkpoint the player touched this level; falls and hazard hits respawn here
+#[derive(Resource, Default)]
+pub struct ActiveCheckpoint {
+    pub respawn_point: Option<Vec3>,
+}
+
+impl ActiveCheckpoint {
+    pub fn respawn_point_or(&self, level_spawn: Vec3) -> Vec3 {
+        self.respawn_point.unwrap_or(level_spawn)
+    }
+}
+
+// Seed for anything random in a run; recorded in replays so playback sees the same world
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct RunSeed(pub u64);
+
+impl Default for RunSeed {
+    fn default() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or(0);
+        Self(nanos)
+    }
+}
+
+// Rapier steps at this fixed rate so identical input gives identical physics
+pub const PHYSICS_TIMESTEP: f32 = 1.0 / 60.0;
+
+// `PHYSICS_TIMESTEP` as the `FixedUpdate` period
+pub fn physics_timestep() -> Duration {
+    Duration::from_secs_f32(PHYSICS_TIMESTEP)
+}
+
+#[derive(Resource)]
+pub struct GameState {
+    pub score: u32,
+    pub lives: u32,
+    pub level: u32,
+    pub collectibles_in_level: u32,
+}
+// ===== PLAYER MODEL =====
+// One mesh of the player's look; shared by the live player and its ghost
+pub struct ModelPart {
+    pub mesh: Mesh,
+    pub color: Color,
+    pub transform: Transform, // Relative to the head sphere
+}
+
+impl ModelPart {
+    pub fn bundle(self, meshes: &mut Assets<Mesh>, materials: &mut Assets<StandardMaterial>) -> PbrBundle {
+        PbrBundle {
+            mesh: meshes.add(self.mesh),
+            material: materials.add(self.color.into()), // Translucent colors blend automatically
+            transform: self.transform,
+            ..default()
+        }
+    }
+}
+
+// Head, body, left arm, right arm, left leg, right leg
+pub fn player_model() -> [ModelPart; 6] {
+    let arm = || Mesh::from(shape::Cylinder {
+        radius: 0.1,
+        height: 0.8,
+        resolution: 6,
+        segments: 1,
+    });
+    let leg = || Mesh::from(shape::Cylinder {
+        radius: 0.15,
+        height: 1.0,
+        resolution: 6,
+        segments: 1,
+    });
+
+    [
+        ModelPart {
+            mesh: Mesh::from(shape::UVSphere {
+                radius: 0.3, // Slightly larger for better visibility
+                sectors: 12,
+                stacks: 12,
+            }),
+            color: Color::rgb(0.0, 1.0, 0.0), // Bright green
+            transform: Transform::IDENTITY,
+        },
+        ModelPart {
+            mesh: Mesh::from(shape::Cylinder {
+                radius: 0.4,
+                height: 1.0,
+                resolution: 8,
+                segments: 1,
+            }),
+            color: Color::rgb(0.2, 0.6, 1.0), // Bright blue
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
+        },
+        ModelPart {
+            mesh: arm(),
+            color: Color::rgb(1.0, 0.9, 0.8),
+            transform: Transform::from_translation(Vec3::new(-0.6, 0.2, 0.0))
+                .with_rotation(Quat::from_rotation_z(0.3)),
+        },
+        ModelPart {
+            mesh: arm(),
+            color: Color::rgb(1.0, 0.9, 0.8),
+            transform: Transform::from_translation(Vec3::new(0.6, 0.2, 0.0))
+                .with_rotation(Quat::from_rotation_z(-0.3)),
+        },
+        ModelPart {
+            mesh: leg(),
+            color: Color::rgb(0.1, 0.2, 0.8), // Bright blue pants
+            transform: Transform::from_translation(Vec3::new(-0.2, -0.5, 0.0)),
+        },
+        ModelPart {
+            mesh: leg(),
+            color: Color::rgb(0.1, 0.2, 0.8),
+            transform: Transform::from_translation(Vec3::new(0.2, -0.5, 0.0)),
+        },
+    ]
+}
+
+// ===== ENHANCED SETUP SYSTEM =====
+fn setup_game(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    tuning: Res<Tuning>,
+) {
+    // ===== ENHANCED CAMERA FOR EXTREME LEVELS =====
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 35.0, 50.0) // Much higher and further back
+                .looking_at(Vec3::new(0.0, 15.0, 0.0), Vec3::Y),
+            ..default()
+        },
+        MainCamera,
+        OrbitCamera::default(),
+    ));
+
+    // ===== ENHANCED PLAYER WITH BETTER VISIBILITY =====
+    let spawn_pos = Vec3::new(0.0, 2.0, 0.0);
+    
+    let [head, body, left_arm, right_arm, left_leg, right_leg] = player_model();
+
+    let player_entity = commands.spawn((
+        PbrBundle {
+            transform: Transform::from_translation(spawn_pos + Vec3::new(0.0, 0.8, 0.0)),
+            ..head.bundle(&mut meshes, &mut materials)
+        },
+        RigidBody::Dynamic,
+        Collider::ball(PLAYER_RADIUS),
+        Velocity::default(),
+        LockedAxes::ROTATION_LOCKED,
+        Ccd::enabled(), // Don't tunnel through fast platforms
+        Player {
+            speed: tuning.player_speed,
+            jump_force: tuning.jump_force,
+            is_grounded: false,
+            coyote_timer: 0.0,
+            jump_buffer_timer: 0.0,
+            jump_pressed: false,
+            invulnerable_timer: 0.0,
+            animation_timer: 0.0,
+            ground_entity: None,
+            ground_velocity: Vec3::ZERO,
+            ground_kind: PlatformKind::Solid,
+        },
+        ActiveEffects::default(),
+        GravityScale(1.0), // Lowered while the low gravity power-up is active
+        SpawnPoint,
+        ColliderMassProperties::Density(1.0),
+        Friction::coefficient(0.7),
+        Restitution::coefficient(0.1),
+    )).id();
+    
+    // Enhanced body parts with brighter colors
+    commands.spawn((body.bundle(&mut meshes, &mut materials), RigidBody::Fixed)).set_parent(player_entity);
+    
+    // Arms (Left and Right)
+    commands.spawn((left_arm.bundle(&mut meshes, &mut materials), RigidBody::Fixed, LeftArm)).set_parent(player_entity);
+    commands.spawn((right_arm.bundle(&mut meshes, &mut materials), RigidBody::Fixed, RightArm)).set_parent(player_entity);
+    
+    // Legs (Left and Right)
+    commands.spawn((left_leg.bundle(&mut meshes, &mut materials), RigidBody::Fixed, LeftLeg)).set_parent(player_entity);
+    commands.spawn((right_leg.bundle(&mut meshes, &mut materials), RigidBody::Fixed, RightLeg)).set_parent(player_entity);
+
+    // ===== GROUND PLATFORM =====
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(25.0, 1.0, 25.0))), // Larger ground
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(0.0, -0.5, 0.0),
+            ..default()
+        },
+        RigidBody::Fixed,
+        Collider::cuboid(12.5, 0.5, 12.5),
+    ));
+
+    // ===== ENHANCED UI =====
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "🔥 EXTREME Level: ",
+                TextStyle {
+                    font_size: 42.0,
+                    color: Color::RED,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "1",
+                TextStyle {
+                    font_size: 42.0,
+                    color: Color::CYAN,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                " | Score: ",
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "0",
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::GOLD,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                " | Lives: ",
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "3",
+                TextStyle {
+                    font_size: 40.0,
+                    color: Color::RED,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "\n⚠️ NIGHTMARE DIFFICULTY - Press R to Reset ⚠️",
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::ORANGE_RED,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        GameUI,
+    ));
+}
+
+// ===== LEVEL LOADING SYSTEMS =====
+fn load_levels(asset_server: Res<AssetServer>, mut library: ResMut<LevelLibrary>) {
+    library.handles = (1..=level::LEVEL_FILE_COUNT)
+        .map(|level| asset_server.load(level::level_path(level)))
+        .collect();
+}
+
+fn spawn_pending_level(
+    mut commands: Commands,
+    mut active_level: ResMut<ActiveLevel>,
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+    mut game_state: ResMut<GameState>,
+    mut player_query: Query<(&mut Transform, &mut Velocity, &Player)>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<LevelData>>,
+    procedural: Res<ProceduralLevels>,
+    run_seed: Res<RunSeed>,
+    difficulty: Res<Difficulty>,
+    rapier_config: Res<RapierConfiguration>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(level) = active_level.pending else {
+        return;
+    };
+
+    let generated = procedural.is_generated(level);
+    let mut data = if generated {
+        // Validate gaps against the real player and the physics world's gravity
+        let arc = player_query.iter().next().map_or_else(JumpArc::default, |(_, _, player)| JumpArc {
+            speed: player.speed,
+            jump_force: player.jump_force,
+            gravity: -rapier_config.gravity.y,
+            ..default()
+        });
+        generator::generate_level(level, generator::level_seed(run_seed.0, level), &arc)
+    } else {
+        // Wait until the level file has finished loading
+        let Some(data) = library.get(level).and_then(|handle| levels.get(handle)) else {
+            return;
+        };
+        data.clone()
+    };
+    difficulty::apply_difficulty(*difficulty, level, &mut data, generated);
+
+    level::spawn_level_content(&mut commands, &mut meshes, &mut materials, level, &data);
+
+    active_level.pending = None;
+    active_level.spawn_point = Vec3::from(data.spawn_point);
+    game_state.collectibles_in_level = data.collectibles.len() as u32;
+    if data.collectibles.is_empty() {
+        warn!("Level {} has no collectibles, so it can't be completed", level);
+    }
+    if std::mem::take(&mut active_level.keep_player) {
+        return; // Rebuilt around the player after the file changed
+    }
+    active_checkpoint.respawn_point = None; // Checkpoints don't carry over between levels
+
+    // Place the player at the level's spawn point
+    for (mut transform, mut velocity, _) in &mut player_query {
+        transform.translation = active_level.spawn_point;
+        velocity.linvel = Vec3::ZERO;
+        velocity.angvel = Vec3::ZERO;
+    }
+}
+
+// Saving the current level's file rebuilds it in place. The editor keeps its own
+// copy, so files saved from it are picked up the next time the level spawns.
+fn reload_modified_level(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<LevelData>>,
+    mut active_level: ResMut<ActiveLevel>,
+    game_state: Res<GameState>,
+    library: Res<LevelLibrary>,
+    procedural: Res<ProceduralLevels>,
+    state: Res<State<AppState>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+) {
+    let Some(current) = library.get(game_state.level).map(Handle::id) else {
+        return;
+    };
+    let modified = events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { id } if *id == current));
+    if !modified
+        || active_level.pending.is_some()
+        || procedural.is_generated(game_state.level)
+        || *state.get() == AppState::Editor
+    {
+        return;
+    }
+
+    for entity in &level_entities {
+        commands.entity(entity).despawn();
+    }
+    active_level.pending = Some(game_state.level);
+    active_level.keep_player = true;
+    info!("🔄 Level {} changed on disk, rebuilding", game_state.level);
+}
+
+// ===== ENHANCED PLAYER MOVEMENT SYSTEMS =====
+fn player_movement(
+    mut player_query: Query<(&mut Velocity, &mut Player, &ActiveEffects)>,
+    camera_query: Query<&OrbitCamera>,
+    action_axes: Res<ActionAxes>,
+    time: Res<Time>,
+) {
+    for (mut velocity, mut player, effects) in &mut player_query {
+        let player_speed = player.speed * effects.speed_multiplier();
+
+        // A half-tilted stick walks at half speed
+        let input = action_axes.movement;
+        let mut movement = Vec3::new(input.x, 0.0, -input.y);
+
+        // Forward is wherever the camera faces
+        if let Ok(orbit) = camera_query.get_single() {
+            movement = orbit.relative(movement);
+        }
+        movement *= player_speed;
+        player.animation_timer += 0.02 + 0.08 * input.length();
+
+        // Walking is relative to whatever the player stands on
+        let target = movement + player.ground_velocity;
+        if let PlatformKind::Ice { .. } = player.ground_kind {
+            // Momentum carries: only ease toward the wanted speed
+            let control = (ICE_CONTROL * time.delta_seconds()).min(1.0);
+            velocity.linvel.x += (target.x - velocity.linvel.x) * control;
+            velocity.linvel.z += (target.z - velocity.linvel.z) * control;
+        } else {
+            velocity.linvel.x = target.x;
+            velocity.linvel.z = target.z;
+        }
+    }
+}
+
+fn detect_ground(
+    rapier_context: Res<RapierContext>,
+    jump_settings: Res<JumpSettings>,
+    mut player_query: Query<(Entity, &Transform, &Velocity, &mut Player)>,
+    platform_query: Query<&MovingPlatform>,
+    kind_query: Query<&PlatformKind>,
+    mut landed_events: EventWriter<PlayerLanded>,
+    time: Res<Time>,
+) {
+    // Slightly thinner than the player so walls touching the sides don't count as ground
+    let probe = Collider::ball(PLAYER_RADIUS * 0.9);
+
+    for (entity, transform, velocity, mut player) in &mut player_query {
+        let hit = rapier_context.cast_shape(
+            transform.translation,
+            Quat::IDENTITY,
+            Vec3::NEG_Y,
+            &probe,
+            jump_settings.ground_check_distance + PLAYER_RADIUS * 0.1,
+            QueryFilter::new().exclude_rigid_body(entity).exclude_sensors(),
+        );
+
+        // Only surfaces facing mostly upward are standable
+        let ground = hit.filter(|(_, toi)| toi.status == TOIStatus::Penetrating || toi.normal1.y > 0.5);
+        // Still rising means the ground is only what a jump just left
+        if ground.is_some() && !player.is_grounded && velocity.linvel.y <= 0.0 {
+            landed_events.send(PlayerLanded {
+                fall_speed: -velocity.linvel.y,
+            });
+        }
+        player.is_grounded = ground.is_some();
+        player.ground_entity = ground.map(|(ground_entity, _)| ground_entity);
+        player.ground_kind = player
+            .ground_entity
+            .and_then(|ground_entity| kind_query.get(ground_entity).ok())
+            .copied()
+            .unwrap_or_default();
+        player.ground_velocity = player
+            .ground_entity
+            .and_then(|ground_entity| platform_query.get(ground_entity).ok())
+            .map_or(Vec3::ZERO, |platform| platform.velocity);
+        if let PlatformKind::Conveyor { velocity } = player.ground_kind {
+            player.ground_velocity += Vec3::from(velocity);
+        }
+
+        if player.is_grounded {
+            player.coyote_timer = jump_settings.coyote_time;
+        } else {
+            player.coyote_timer = (player.coyote_timer - time.delta_seconds()).max(0.0);
+        }
+    }
+}
+
+// Presses are caught every frame and kept for the next physics step, which may not
+// run this frame
+fn buffer_jump_press(mut player_query: Query<&mut Player>, actions: Res<Input<Action>>) {
+    if actions.just_pressed(Action::Jump) {
+        for mut player in &mut player_query {
+            player.jump_pressed = true;
+        }
+    }
+}
+
+fn player_jump(
+    mut player_query: Query<(&mut Velocity, &mut Player, &mut ActiveEffects)>,
+    jump_settings: Res<JumpSettings>,
+    mut jumped_events: EventWriter<PlayerJumped>,
+    time: Res<Time>,
+) {
+    for (mut velocity, mut player, mut effects) in &mut player_query {
+        if player.is_grounded {
+            effects.air_jump_used = false;
+        }
+
+        // Buffer the press so jumping just before landing still works
+        let pressed = std::mem::take(&mut player.jump_pressed);
+        if pressed {
+            player.jump_buffer_timer = jump_settings.jump_buffer;
+        } else {
+            player.jump_buffer_timer = (player.jump_buffer_timer - time.delta_seconds()).max(0.0);
+        }
+
+        // Coyote time allows the jump shortly after walking off an edge
+        if player.jump_buffer_timer > 0.0 && player.coyote_timer > 0.0 {
+            velocity.linvel.y = player.jump_force;
+            player.jump_buffer_timer = 0.0;
+            player.coyote_timer = 0.0;
+            player.is_grounded = false;
+            jumped_events.send(PlayerJumped { air_jump: false });
+        } else if pressed && effects.has(PowerUpKind::DoubleJump) && !effects.air_jump_used {
+            // Second jump in mid-air
+            velocity.linvel.y = player.jump_force;
+            player.jump_buffer_timer = 0.0;
+            effects.air_jump_used = true;
+            jumped_events.send(PlayerJumped { air_jump: true });
+        }
+    }
+}
+
+// ===== ENHANCED PLAYER ANIMATION SYSTEM =====
+fn animate_player(
+    player_query: Query<&Player>,
+    mut left_arm_query: Query<&mut Transform, (With<LeftArm>, Without<RightArm>, Without<LeftLeg>, Without<RightLeg>)>,
+    mut right_arm_query: Query<&mut Transform, (With<RightArm>, Without<LeftArm>, Without<LeftLeg>, Without<RightLeg>)>,
+    mut left_leg_query: Query<&mut Transform, (With<LeftLeg>, Without<LeftArm>, Without<RightArm>, Without<RightLeg>)>,
+    mut right_leg_query: Query<&mut Transform, (With<RightLeg>, Without<LeftArm>, Without<RightArm>, Without<LeftLeg>)>,
+) {
+    if let Ok(player) = player_query.get_single() {
+        let swing_angle = (player.animation_timer * 6.0).sin() * 0.6; // More dramatic animation
+        let bounce_factor = (player.animation_timer * 12.0).sin().abs() * 0.1;
+        
+        if let Ok(mut transform) = left_arm_query.get_single_mut() {
+            transform.rotation = Quat::from_rotation_x(swing_angle) * Quat::from_rotation_z(0.3);
+            transform.translation = Vec3::new(-0.6, 0.2 + bounce_factor, 0.0);
+        }
+        
+        if let Ok(mut transform) = right_arm_query.get_single_mut() {
+            transform.rotation = Quat::from_rotation_x(-swing_angle) * Quat::from_rotation_z(-0.3);
+            transform.translation = Vec3::new(0.6, 0.2 + bounce_factor, 0.0);
+        }
+        
+        if let Ok(mut transform) = left_leg_query.get_single_mut() {
+            transform.rotation = Quat::from_rotation_x(-swing_angle * 0.8);
+            transform.translation = Vec3::new(-0.2, -0.5 + bounce_factor, 0.0);
+        }
+        
+        if let Ok(mut transform) = right_leg_query.get_single_mut() {
+            transform.rotation = Quat::from_rotation_x(swing_angle * 0.8);
+            transform.translation = Vec3::new(0.2, -0.5 + bounce_factor, 0.0);
+        }
+    }
+}
+
+// ===== ENHANCED GAME LOGIC SYSTEMS =====
+// Picks up every collectible whose sensor the player entered since the last frame
+fn collect_items(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<Entity, With<Player>>,
+    collectible_query: Query<(&Collectible, &Transform)>,
+    rules: Res<ScoringRules>,
+    mut level_score: ResMut<LevelScore>,
+    clock: Res<RunClock>,
+    mut collected_events: EventWriter<ItemCollected>,
+    mut collected: Local<Vec<Entity>>,
+) {
+    collected.clear();
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(first, second, _) = *event else {
+            continue;
+        };
+        let other = if first == player_entity {
+            second
+        } else if second == player_entity {
+            first
+        } else {
+            continue;
+        };
+
+        // Already despawned this frame, don't count it twice
+        if collected.contains(&other) {
+            continue;
+        }
+
+        if let Ok((collectible, transform)) = collectible_query.get(other) {
+            collected.push(other);
+            let points = level_score.collect(&rules, collectible.value, clock.run_seconds);
+            let bonus_lives = scoring::award(&mut game_state, &rules, points);
+            level_score.bonus_lives += bonus_lives;
+            collected_events.send(ItemCollected {
+                value: collectible.value,
+                points,
+                combo: level_score.combo,
+                bonus_lives,
+                position: transform.translation,
+            });
+            commands.entity(other).despawn();
+        }
+    }
+}
+
+fn activate_checkpoints(
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+    player_query: Query<&Transform, With<Player>>,
+    mut checkpoint_query: Query<(&Transform, &mut Checkpoint, &Handle<StandardMaterial>), Without<Player>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut checkpoint_events: EventWriter<CheckpointReached>,
+) {
+    if let Ok(player_transform) = player_query.get_single() {
+        for (checkpoint_transform, mut checkpoint, material) in &mut checkpoint_query {
+            if checkpoint.activated {
+                continue;
+            }
+
+            let distance = player_transform.translation.distance(checkpoint_transform.translation);
+            if distance < 1.5 {
+                checkpoint.activated = true;
+                active_checkpoint.respawn_point = Some(checkpoint.respawn_point);
+
+                // Light up the marker so the player knows it's saved
+                if let Some(material) = materials.get_mut(material) {
+                    material.base_color = Color::rgb(0.0, 1.0, 0.4);
+                    material.emissive = Color::rgb(0.0, 0.5, 0.2);
+                }
+
+                checkpoint_events.send(CheckpointReached {
+                    respawn_point: checkpoint.respawn_point,
+                });
+            }
+        }
+    }
+}
+
+fn check_level_complete(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut splits: ResMut<RunSplits>,
+    clock: Res<RunClock>,
+    rules: Res<ScoringRules>,
+    mut level_score: ResMut<LevelScore>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut completed_events: EventWriter<LevelCompleted>,
+    collectible_query: Query<Entity, With<Collectible>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+) {
+    // A level that never had collectibles isn't complete just because none are left
+    let spawned = game_state.collectibles_in_level > 0;
+    if active_level.pending.is_none() && spawned && collectible_query.is_empty() {
+        let completed_level = game_state.level;
+        game_state.level += 1;
+        splits.split(clock.run_seconds);
+        
+        // Clean up current level entities
+        for entity in &level_entities {
+            commands.entity(entity).despawn();
+        }
+        
+        // Queue the next level; the player is moved to its spawn point once it loads
+        active_level.pending = Some(game_state.level);
+        next_state.set(AppState::LevelTransition);
+        
+        // Enhanced level completion bonuses
+        let completion_bonus = match completed_level {
+            1..=2 => 1,
+            3..=4 => 2,
+            _ => 3,
+        };
+        
+        game_state.lives += completion_bonus;
+        let items = game_state.collectibles_in_level;
+        *breakdown = scoring::finish_level(
+            &mut game_state,
+            &mut level_score,
+            &rules,
+            completed_level,
+            items,
+            clock.run_seconds,
+        );
+        breakdown.bonus_lives += completion_bonus;
+
+        completed_events.send(LevelCompleted {
+            level: completed_level,
+            bonus_points: breakdown.bonus_points(),
+            bonus_lives: breakdown.bonus_lives,
+            score: game_state.score,
+            lives: game_state.lives,
+        });
+    }
+}
+// ===== ENHANCED HAZARD AND RESPAWN SYSTEMS =====
+// Hazards are checked against the sensors the player currently overlaps rather than
+// collision events, so standing in one when invulnerability runs out still hurts
+fn check_hazards(
+    mut game_state: ResMut<GameState>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Velocity, &mut Player, &mut ActiveEffects)>,
+    hazard_query: Query<&Transform, (With<Hazard>, Without<Player>)>,
+    rapier_context: Res<RapierContext>,
+    active_level: Res<ActiveLevel>,
+    active_checkpoint: Res<ActiveCheckpoint>,
+    difficulty: Res<Difficulty>,
+    mut level_score: ResMut<LevelScore>,
+    mut damaged_events: EventWriter<PlayerDamaged>,
+    time: Res<Time>,
+) {
+    for (player_entity, mut player_transform, mut velocity, mut player, mut effects) in &mut player_query {
+        if player.invulnerable_timer > 0.0 {
+            player.invulnerable_timer -= time.delta_seconds();
+        }
+        
+        if player.invulnerable_timer <= 0.0 {
+            let touching = rapier_context
+                .intersection_pairs_with(player_entity)
+                .filter(|(_, _, intersecting)| *intersecting)
+                .map(|(first, second, _)| if first == player_entity { second } else { first });
+
+            for other in touching {
+                let Ok(hazard_transform) = hazard_query.get(other) else {
+                    continue;
+                };
+
+                // A shield charge takes the hit instead of a life
+                if effects.absorb_hit() {
+                    player.invulnerable_timer = SHIELD_INVULNERABILITY;
+                    damaged_events.send(PlayerDamaged {
+                        hazard_position: hazard_transform.translation,
+                        absorbed: true,
+                        lives: game_state.lives,
+                    });
+                    break;
+                }
+
+                if game_state.lives > 0 {
+                    game_state.lives -= 1;
+                    level_score.take_damage();
+                    player.invulnerable_timer = difficulty.settings().invulnerability;
+                    damaged_events.send(PlayerDamaged {
+                        hazard_position: hazard_transform.translation,
+                        absorbed: false,
+                        lives: game_state.lives,
+                    });
+
+                    // Send the player back to the last checkpoint
+                    player_transform.translation =
+                        active_checkpoint.respawn_point_or(active_level.spawn_point);
+                    velocity.linvel = Vec3::ZERO;
+                    velocity.angvel = Vec3::ZERO;
+                }
+                break;
+            }
+        }
+    }
+}
+
+fn respawn_player(
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut game_state: ResMut<GameState>,
+    active_level: Res<ActiveLevel>,
+    active_checkpoint: Res<ActiveCheckpoint>,
+    mut level_score: ResMut<LevelScore>,
+    mut fell_events: EventWriter<PlayerFell>,
+) {
+    for (mut transform, mut velocity) in &mut player_query {
+        // Much deeper fall threshold for extreme levels
+        if transform.translation.y < -30.0 {
+            let depth = -transform.translation.y;
+            transform.translation = active_checkpoint.respawn_point_or(active_level.spawn_point);
+            velocity.linvel = Vec3::ZERO;
+            velocity.angvel = Vec3::ZERO;
+            
+            if game_state.lives > 0 {
+                game_state.lives -= 1;
+                level_score.take_damage();
+            }
+            fell_events.send(PlayerFell {
+                depth,
+                lives: game_state.lives,
+            });
+        }
+    }
+}
+
+// ===== ENHANCED MOVING PLATFORM SYSTEM =====
+// ===== ENHANCED UI SYSTEM =====
+fn update_ui(
+    game_state: Res<GameState>,
+    mut ui_query: Query<&mut Text, With<GameUI>>,
+    player_query: Query<&Transform, With<Player>>,
+    rules: Res<ScoringRules>,
+    level_score: Res<LevelScore>,
+    clock: Res<RunClock>,
+) {
+    for mut text in &mut ui_query {
+        text.sections[1].value = game_state.level.to_string();
+        text.sections[3].value = game_state.score.to_string();
+
+        // The multiplier the next pickup gets while a chain is going
+        if level_score.combo_live(&rules, clock.run_seconds) {
+            let multiplier = LevelScore::multiplier(&rules, level_score.combo + 1);
+            text.sections[3].value += &format!(" 🔥 x{:.1}", multiplier);
+        }
+        text.sections[5].value = game_state.lives.to_string();
+        
+        // Dynamic UI color changes based on lives and level
+        text.sections[5].style.color = match game_state.lives {
+            0 => Color::DARK_GRAY,
+            1 => Color::RED,
+            2 => Color::ORANGE_RED,
+            3..=5 => Color::YELLOW,
+            _ => Color::GREEN,
+        };
+        
+        // Level indicator color based on difficulty
+        text.sections[1].style.color = match game_state.level {
+            1..=2 => Color::CYAN,
+            3..=4 => Color::ORANGE,
+            _ => Color::RED,
+        };
+        
+        // Add height indicator if player is at extreme height
+        if let Ok(player_transform) = player_query.get_single() {
+            if player_transform.translation.y > 50.0 {
+                text.sections[6].value = format!(
+                    "\n🏔️ ALTITUDE: {:.0}m | ⚠️ NIGHTMARE DIFFICULTY - Press R to Reset ⚠️", 
+                    player_transform.translation.y
+                );
+                text.sections[6].style.color = Color::GOLD;
+            } else {
+                text.sections[6].value = "\n⚠️ NIGHTMARE DIFFICULTY - Press R to Reset ⚠️".to_string();
+                text.sections[6].style.color = Color::ORANGE_RED;
+            }
+        }
+    }
+}
+
+// ===== ENHANCED RESET SYSTEM =====
+fn reset_game(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut player_query: Query<(&mut Velocity, &mut ActiveEffects), With<Player>>,
+    actions: Res<Input<Action>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    mut restart_events: EventReader<RestartRequested>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut profile: ResMut<Profile>,
+    profile_path: Res<ProfilePath>,
+    mut clock: ResMut<RunClock>,
+    mut splits: ResMut<RunSplits>,
+    (mut reset_events, mut record_events): (EventWriter<GameReset>, RunRecordEvents),
+    difficulty: Res<Difficulty>,
+    editor: Option<Res<LevelEditor>>,
+) {
+    // R restarts straight into play during a run or from the game over screen
+    let key_reset = actions.just_pressed(Action::Reset)
+        && matches!(state.get(), AppState::Playing | AppState::Paused | AppState::GameOver);
+    let requested = restart_events.read().count() > 0;
+
+    if key_reset || requested {
+        reset_events.send(GameReset {
+            level: game_state.level,
+            score: game_state.score,
+            lives: game_state.lives,
+        });
+
+        // Abandoned runs still count; game overs were already recorded on the game over screen
+        if game_state.lives > 0 && game_state.score > 0 && editor::not_play_testing(editor) {
+            if let Some(rank) = save::record_run(&mut profile, &game_state, &clock) {
+                record_events.ranks.send(rank);
+            }
+            if let Some(best) = speedrun::finish_run(&mut profile, &mut splits, *difficulty, &profile_path) {
+                record_events.personal_bests.send(best);
+            }
+            save::save_profile(&profile_path, &profile);
+        }
+        *clock = RunClock::default();
+        *splits = RunSplits::default();
+        
+        // Reset game state
+        game_state.score = 0;
+        game_state.lives = difficulty.settings().starting_lives;
+        game_state.level = 1;
+        game_state.collectibles_in_level = 8;
+        
+        // Stop the player and drop any power-ups; they are moved to the spawn point once level 1 loads
+        for (mut velocity, mut effects) in &mut player_query {
+            velocity.linvel = Vec3::ZERO;
+            velocity.angvel = Vec3::ZERO;
+            effects.clear();
+        }
+        
+        // Remove all existing level entities
+        for entity in &level_entities {
+            commands.entity(entity).despawn();
+        }
+        
+        // Respawn level 1 with extreme difficulty
+        active_level.pending = Some(1);
+
+        if key_reset {
+            next_state.set(AppState::Playing);
+        }
+    }
+}
+
+// ===== GAME OVER DETECTION SYSTEM =====
+fn check_game_over(
+    game_state: Res<GameState>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut game_over_events: EventWriter<GameOver>,
+) {
+    if game_state.lives == 0 {
+        next_state.set(AppState::GameOver);
+        game_over_events.send(GameOver {
+            level: game_state.level,
+            score: game_state.score,
+        });
+    }
+}
+
+// Physics only advances while actually playing; menus and overlays freeze the world
+fn pause_physics(state: Res<State<AppState>>, mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.physics_pipeline_active = *state.get() == AppState::Playing;
+}
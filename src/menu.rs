@@ -0,0 +1,587 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use crate::controls::{save_bindings, Action, InputBindings};
+use crate::difficulty::Difficulty;
+use crate::level::ActiveLevel;
+use crate::save::{format_time, save_profile, Profile, ProfilePath, DEFAULT_PLAYER_NAME, PLAYER_NAME_MAX_CHARS};
+use crate::scoring::ScoreBreakdown;
+use crate::settings::{save_settings, GameSettings, FOV_RANGE, RESOLUTIONS, SMOOTHING_RANGE, WINDOW_MODES};
+use crate::{AppState, GameState, RestartRequested};
+
+// How long the "level complete" screen stays up before play resumes; jumping skips it
+pub const LEVEL_TRANSITION_SECONDS: f32 = 5.0;
+// Rows of the options screen shown at once; the list scrolls with the cursor
+pub const VISIBLE_OPTION_ROWS: usize = 11;
+
+// ===== MENU PLUGIN =====
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTransitionTimer>()
+            .init_resource::<OptionsMenu>()
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(AppState::Paused), spawn_pause_screen)
+            .add_systems(OnEnter(AppState::LevelTransition), spawn_level_transition)
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen)
+            .add_systems(OnEnter(AppState::Leaderboard), spawn_leaderboard_screen)
+            .add_systems(OnEnter(AppState::Options), spawn_options_screen)
+            .add_systems(OnExit(AppState::MainMenu), despawn_screen)
+            .add_systems(OnExit(AppState::Paused), despawn_screen)
+            .add_systems(OnExit(AppState::LevelTransition), despawn_screen)
+            .add_systems(OnExit(AppState::GameOver), despawn_screen)
+            .add_systems(OnExit(AppState::Leaderboard), despawn_screen)
+            .add_systems(OnExit(AppState::Options), (despawn_screen, save_options))
+            .add_systems(
+                Update,
+                (
+                    main_menu_input.run_if(in_state(AppState::MainMenu)),
+                    refresh_main_menu
+                        .after(main_menu_input)
+                        .run_if(in_state(AppState::MainMenu).and_then(resource_changed::<Difficulty>())),
+                    pause_input.run_if(in_state(AppState::Playing).or_else(in_state(AppState::Paused))),
+                    finish_level_transition.run_if(in_state(AppState::LevelTransition)),
+                    game_over_input.run_if(in_state(AppState::GameOver)),
+                    leaderboard_input.run_if(in_state(AppState::Leaderboard)),
+                    options_input.run_if(in_state(AppState::Options)),
+                    refresh_options_screen.after(options_input).run_if(
+                        in_state(AppState::Options).and_then(
+                            resource_changed::<OptionsMenu>()
+                                .or_else(resource_changed::<Profile>())
+                                .or_else(resource_changed::<GameSettings>())
+                                .or_else(resource_changed::<InputBindings>()),
+                        ),
+                    ),
+                ),
+            );
+    }
+}
+
+// ===== COMPONENTS AND RESOURCES =====
+#[derive(Component)]
+pub struct StateScreen; // Root node of the overlay for the current state
+
+#[derive(Resource)]
+pub struct LevelTransitionTimer(pub Timer);
+
+impl Default for LevelTransitionTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(LEVEL_TRANSITION_SECONDS, TimerMode::Once))
+    }
+}
+
+// Cursor of the options screen, and the screen to go back to
+#[derive(Resource)]
+pub struct OptionsMenu {
+    pub selected: usize,
+    pub rebinding: bool,    // Waiting for a key for the selected action
+    pub editing_name: bool, // Typing the player name
+    pub return_to: AppState,
+}
+
+impl Default for OptionsMenu {
+    fn default() -> Self {
+        Self {
+            selected: 0,
+            rebinding: false,
+            editing_name: false,
+            return_to: AppState::MainMenu,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OptionRow {
+    PlayerName,
+    WindowMode,
+    Resolution,
+    VSync,
+    Shadows,
+    FieldOfView,
+    CameraSmoothing,
+    MasterVolume,
+    Binding(Action),
+}
+
+const SETTING_ROWS: [OptionRow; 8] = [
+    OptionRow::PlayerName,
+    OptionRow::WindowMode,
+    OptionRow::Resolution,
+    OptionRow::VSync,
+    OptionRow::Shadows,
+    OptionRow::FieldOfView,
+    OptionRow::CameraSmoothing,
+    OptionRow::MasterVolume,
+];
+
+fn option_rows() -> impl Iterator<Item = OptionRow> {
+    SETTING_ROWS.into_iter().chain(Action::ALL.into_iter().map(OptionRow::Binding))
+}
+
+fn option_row_count() -> usize {
+    SETTING_ROWS.len() + Action::ALL.len()
+}
+
+// ===== SCREEN SPAWNING =====
+fn spawn_screen(commands: &mut Commands, lines: &[(String, f32, Color)]) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(16.0),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.7).into(),
+                ..default()
+            },
+            StateScreen,
+        ))
+        .with_children(|parent| {
+            for (text, font_size, color) in lines {
+                parent.spawn(TextBundle::from_section(
+                    text.clone(),
+                    TextStyle {
+                        font_size: *font_size,
+                        color: *color,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+fn spawn_main_menu(mut commands: Commands, difficulty: Res<Difficulty>) {
+    let difficulty_color = match *difficulty {
+        Difficulty::Casual => Color::GREEN,
+        Difficulty::Normal => Color::YELLOW,
+        Difficulty::Extreme => Color::ORANGE_RED,
+        Difficulty::Nightmare => Color::PURPLE,
+    };
+    let settings = difficulty.settings();
+
+    spawn_screen(
+        &mut commands,
+        &[
+            ("🔥 EXTREME 3D PLATFORMER 🔥".to_string(), 64.0, Color::ORANGE_RED),
+            (format!("◀ {} ▶", difficulty.name()), 40.0, difficulty_color),
+            (
+                format!(
+                    "{} lives | {:.1}s invulnerability | {:.0}% hazards",
+                    settings.starting_lives,
+                    settings.invulnerability,
+                    settings.hazard_share * 100.0
+                ),
+                22.0,
+                Color::GRAY,
+            ),
+            (
+                "Press Enter to start | ←/→ difficulty | L leaderboard | O options | E editor".to_string(),
+                32.0,
+                Color::WHITE,
+            ),
+            (
+                "WASD/Arrows to move | Space to jump | Esc to pause | R to reset".to_string(),
+                22.0,
+                Color::GRAY,
+            ),
+        ],
+    );
+}
+
+// Redraws the title menu after the difficulty changes
+fn refresh_main_menu(
+    mut commands: Commands,
+    screens: Query<Entity, With<StateScreen>>,
+    difficulty: Res<Difficulty>,
+) {
+    for entity in &screens {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_main_menu(commands, difficulty);
+}
+
+fn spawn_pause_screen(mut commands: Commands) {
+    spawn_screen(
+        &mut commands,
+        &[
+            ("⏸️ PAUSED".to_string(), 64.0, Color::CYAN),
+            (
+                "Esc / P to resume | R to restart | O for options | Q to quit to menu".to_string(),
+                28.0,
+                Color::WHITE,
+            ),
+        ],
+    );
+}
+
+// Shows how the cleared level was scored
+fn spawn_level_transition(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    breakdown: Res<ScoreBreakdown>,
+    mut timer: ResMut<LevelTransitionTimer>,
+) {
+    timer.0.reset();
+    let row = |label: String, points: u32| {
+        let color = if points > 0 { Color::WHITE } else { Color::GRAY };
+        (format!("{}: +{}", label, points), 28.0, color)
+    };
+    let no_damage = if breakdown.lives_lost == 0 {
+        "No-damage bonus".to_string()
+    } else {
+        format!("No-damage bonus ({} lives lost)", breakdown.lives_lost)
+    };
+
+    let mut lines = vec![
+        (format!("🎯 LEVEL {} CONQUERED!", breakdown.level), 56.0, Color::GOLD),
+        row("Collectibles".to_string(), breakdown.item_points),
+        row(format!("Combo bonus (best chain {})", breakdown.best_combo), breakdown.combo_points),
+        row("Completion bonus".to_string(), breakdown.completion_points),
+        row(no_damage, breakdown.no_damage_points),
+        row(
+            format!("Time bonus ({} / par {})", format_time(breakdown.seconds), format_time(breakdown.par_seconds)),
+            breakdown.time_points,
+        ),
+        (
+            format!("Level total: {} | Score: {}", breakdown.total(), game_state.score),
+            36.0,
+            Color::GOLD,
+        ),
+    ];
+    if breakdown.bonus_lives > 0 {
+        lines.push((format!("❤️ +{} lives", breakdown.bonus_lives), 28.0, Color::GREEN));
+    }
+    lines.push((format!("Get ready for EXTREME Level {}...", game_state.level), 32.0, Color::WHITE));
+    spawn_screen(&mut commands, &lines);
+}
+
+fn spawn_game_over_screen(mut commands: Commands, game_state: Res<GameState>) {
+    spawn_screen(
+        &mut commands,
+        &[
+            ("💀 GAME OVER 💀".to_string(), 72.0, Color::RED),
+            (
+                format!("ELIMINATED at Level {} with {} points!", game_state.level, game_state.score),
+                32.0,
+                Color::WHITE,
+            ),
+            ("R to try again | Esc for main menu".to_string(), 28.0, Color::GRAY),
+        ],
+    );
+}
+
+fn spawn_leaderboard_screen(mut commands: Commands, profile: Res<Profile>) {
+    let mut lines = vec![("🏆 LEADERBOARD 🏆".to_string(), 56.0, Color::GOLD)];
+
+    if profile.leaderboard.is_empty() {
+        lines.push(("No runs yet - go set a record!".to_string(), 28.0, Color::WHITE));
+    }
+    for (i, entry) in profile.leaderboard.iter().enumerate() {
+        lines.push((
+            format!(
+                "#{:<2} {:<12} {:>6} pts | Level {} | {}",
+                i + 1,
+                entry.name,
+                entry.score,
+                entry.level,
+                format_time(entry.time_seconds)
+            ),
+            26.0,
+            if i == 0 { Color::GOLD } else { Color::WHITE },
+        ));
+    }
+
+    lines.push((format!("Highest level reached: {}", profile.highest_level), 26.0, Color::CYAN));
+    let best_times: Vec<String> = profile
+        .best_level_times
+        .iter()
+        .map(|(level, seconds)| format!("L{} {}", level, format_time(*seconds)))
+        .collect();
+    if !best_times.is_empty() {
+        lines.push((format!("Best times: {}", best_times.join(" | ")), 22.0, Color::CYAN));
+    }
+    lines.push(("Esc / L to go back".to_string(), 22.0, Color::GRAY));
+
+    spawn_screen(&mut commands, &lines);
+}
+
+fn spawn_options_screen(
+    mut commands: Commands,
+    menu: Res<OptionsMenu>,
+    profile: Res<Profile>,
+    settings: Res<GameSettings>,
+    bindings: Res<InputBindings>,
+) {
+    let mut lines = vec![("⚙️ OPTIONS".to_string(), 48.0, Color::CYAN)];
+
+    let first = menu
+        .selected
+        .saturating_sub(VISIBLE_OPTION_ROWS / 2)
+        .min(option_row_count().saturating_sub(VISIBLE_OPTION_ROWS));
+    for (i, row) in option_rows().enumerate().skip(first).take(VISIBLE_OPTION_ROWS) {
+        let (label, value) = match row {
+            OptionRow::PlayerName if menu.editing_name => ("Name".to_string(), format!("{}_", profile.player_name)),
+            OptionRow::PlayerName => ("Name".to_string(), profile.player_name.clone()),
+            OptionRow::WindowMode => ("Window mode".to_string(), window_mode_name(settings.window_mode).to_string()),
+            OptionRow::Resolution => (
+                "Resolution".to_string(),
+                format!("{}×{}", settings.resolution.0, settings.resolution.1),
+            ),
+            OptionRow::VSync => ("VSync".to_string(), on_off(settings.vsync).to_string()),
+            OptionRow::Shadows => ("Shadows".to_string(), on_off(settings.shadows).to_string()),
+            OptionRow::FieldOfView => ("Field of view".to_string(), format!("{:.0}°", settings.fov_degrees)),
+            OptionRow::CameraSmoothing => ("Camera smoothing".to_string(), format!("{:.1}", settings.camera_smoothing)),
+            OptionRow::MasterVolume => (
+                "Master volume".to_string(),
+                format!("{:.0}%", settings.master_volume * 100.0),
+            ),
+            OptionRow::Binding(action) if menu.rebinding && i == menu.selected => {
+                (format!("{:?}", action), "press a key...".to_string())
+            }
+            OptionRow::Binding(action) => {
+                let keys: Vec<String> = bindings.keys_for(action).iter().map(|key| format!("{:?}", key)).collect();
+                (format!("{:?}", action), if keys.is_empty() { "-".to_string() } else { keys.join(" / ") })
+            }
+        };
+
+        let line = format!("{}: {}", label, value);
+        if i == menu.selected {
+            lines.push((format!("▶ {} ◀", line), 22.0, Color::YELLOW));
+        } else {
+            lines.push((line, 20.0, Color::WHITE));
+        }
+    }
+
+    lines.push((
+        "↑/↓ select | ←/→ change | Enter to edit the name or rebind a key | Esc to go back".to_string(),
+        20.0,
+        Color::GRAY,
+    ));
+    spawn_screen(&mut commands, &lines);
+}
+
+// Redraws the options screen after the cursor or a setting changes
+fn refresh_options_screen(
+    mut commands: Commands,
+    screens: Query<Entity, With<StateScreen>>,
+    menu: Res<OptionsMenu>,
+    profile: Res<Profile>,
+    settings: Res<GameSettings>,
+    bindings: Res<InputBindings>,
+) {
+    for entity in &screens {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_options_screen(commands, menu, profile, settings, bindings);
+}
+
+fn window_mode_name(mode: WindowMode) -> &'static str {
+    match mode {
+        WindowMode::Windowed => "Windowed",
+        WindowMode::BorderlessFullscreen => "Borderless",
+        WindowMode::SizedFullscreen | WindowMode::Fullscreen => "Fullscreen",
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn despawn_screen(mut commands: Commands, screens: Query<Entity, With<StateScreen>>) {
+    for entity in &screens {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// ===== STATE INPUT SYSTEMS =====
+fn main_menu_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
+    mut difficulty: ResMut<Difficulty>,
+    mut options: ResMut<OptionsMenu>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        next_state.set(AppState::Playing);
+    } else if keyboard_input.just_pressed(KeyCode::L) {
+        next_state.set(AppState::Leaderboard);
+    } else if keyboard_input.just_pressed(KeyCode::O) {
+        options.return_to = AppState::MainMenu;
+        next_state.set(AppState::Options);
+    } else if actions.just_pressed(Action::MoveLeft) {
+        *difficulty = difficulty.cycle(-1);
+    } else if actions.just_pressed(Action::MoveRight) {
+        *difficulty = difficulty.cycle(1);
+    }
+}
+
+fn leaderboard_input(keyboard_input: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<AppState>>) {
+    if keyboard_input.just_pressed(KeyCode::Escape) || keyboard_input.just_pressed(KeyCode::L) {
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+fn pause_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
+    state: Res<State<AppState>>,
+    mut options: ResMut<OptionsMenu>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut restart_events: EventWriter<RestartRequested>,
+) {
+    let toggle = actions.just_pressed(Action::Pause);
+
+    match state.get() {
+        AppState::Playing if toggle => next_state.set(AppState::Paused),
+        AppState::Paused if toggle => next_state.set(AppState::Playing),
+        AppState::Paused if keyboard_input.just_pressed(KeyCode::O) => {
+            options.return_to = AppState::Paused;
+            next_state.set(AppState::Options);
+        }
+        AppState::Paused if keyboard_input.just_pressed(KeyCode::Q) => {
+            // Quitting abandons the run so the menu starts fresh
+            restart_events.send(RestartRequested);
+            next_state.set(AppState::MainMenu);
+        }
+        _ => {}
+    }
+}
+
+// Changes apply as soon as they are made; leaving the screen saves them
+fn options_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
+    mut typed: EventReader<ReceivedCharacter>,
+    mut menu: ResMut<OptionsMenu>,
+    mut profile: ResMut<Profile>,
+    mut settings: ResMut<GameSettings>,
+    mut bindings: ResMut<InputBindings>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let row = option_rows().nth(menu.selected).unwrap_or(OptionRow::WindowMode);
+
+    // Characters typed before editing started, like the Enter that starts it, are dropped
+    let typed: String = typed.read().map(|event| event.char).filter(|c| !c.is_control()).collect();
+    if menu.editing_name {
+        // Enter or Escape finishes; an emptied name goes back to the default
+        if keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::Escape]) {
+            let name = profile.player_name.trim().to_string();
+            profile.player_name = if name.is_empty() { DEFAULT_PLAYER_NAME.to_string() } else { name };
+            menu.editing_name = false;
+        } else if keyboard_input.just_pressed(KeyCode::Back) {
+            profile.player_name.pop();
+        } else {
+            let room = PLAYER_NAME_MAX_CHARS.saturating_sub(profile.player_name.chars().count());
+            profile.player_name.extend(typed.chars().take(room));
+        }
+        return;
+    }
+
+    if menu.rebinding {
+        // The next key pressed replaces the action's keys; Escape keeps them
+        if let Some(key) = keyboard_input.get_just_pressed().next() {
+            if let (OptionRow::Binding(action), false) = (row, *key == KeyCode::Escape) {
+                bindings.keys.insert(action, vec![*key]);
+            }
+            menu.rebinding = false;
+        }
+        return;
+    }
+
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(menu.return_to);
+        return;
+    }
+    if actions.just_pressed(Action::MoveBack) {
+        menu.selected = (menu.selected + 1) % option_row_count();
+        return;
+    }
+    if actions.just_pressed(Action::MoveForward) {
+        menu.selected = (menu.selected + option_row_count() - 1) % option_row_count();
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        menu.rebinding = matches!(row, OptionRow::Binding(_));
+        menu.editing_name = row == OptionRow::PlayerName;
+        return;
+    }
+
+    let step = actions.just_pressed(Action::MoveRight) as i32 - actions.just_pressed(Action::MoveLeft) as i32;
+    if step == 0 {
+        return;
+    }
+    let cycle = |index: usize, len: usize| (index as i32 + step).rem_euclid(len as i32) as usize;
+    match row {
+        OptionRow::WindowMode => {
+            let current = WINDOW_MODES.iter().position(|mode| *mode == settings.window_mode).unwrap_or(0);
+            settings.window_mode = WINDOW_MODES[cycle(current, WINDOW_MODES.len())];
+        }
+        OptionRow::Resolution => {
+            let current = RESOLUTIONS.iter().position(|size| *size == settings.resolution).unwrap_or(0);
+            settings.resolution = RESOLUTIONS[cycle(current, RESOLUTIONS.len())];
+        }
+        OptionRow::VSync => settings.vsync = !settings.vsync,
+        OptionRow::Shadows => settings.shadows = !settings.shadows,
+        OptionRow::FieldOfView => {
+            settings.fov_degrees = (settings.fov_degrees + step as f32 * 5.0).clamp(FOV_RANGE.0, FOV_RANGE.1);
+        }
+        OptionRow::CameraSmoothing => {
+            settings.camera_smoothing =
+                (settings.camera_smoothing + step as f32 * 0.5).clamp(SMOOTHING_RANGE.0, SMOOTHING_RANGE.1);
+        }
+        OptionRow::MasterVolume => {
+            // Whole tenths, so repeated steps don't drift
+            let tenths = ((settings.master_volume + step as f32 * 0.1) * 10.0).round();
+            settings.master_volume = tenths.clamp(0.0, 10.0) / 10.0;
+        }
+        OptionRow::PlayerName | OptionRow::Binding(_) => {}
+    }
+}
+
+fn save_options(
+    profile_path: Res<ProfilePath>,
+    profile: Res<Profile>,
+    settings: Res<GameSettings>,
+    bindings: Res<InputBindings>,
+) {
+    save_profile(&profile_path, &profile);
+    save_settings(&profile_path, &settings);
+    save_bindings(&profile_path, &bindings);
+}
+
+fn finish_level_transition(
+    time: Res<Time>,
+    actions: Res<Input<Action>>,
+    active_level: Res<ActiveLevel>,
+    mut timer: ResMut<LevelTransitionTimer>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    timer.0.tick(time.delta());
+
+    // Wait for the next level to finish loading as well
+    let done = timer.0.finished() || actions.just_pressed(Action::Jump);
+    if done && active_level.pending.is_none() {
+        next_state.set(AppState::Playing);
+    }
+}
+
+fn game_over_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut restart_events: EventWriter<RestartRequested>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        restart_events.send(RestartRequested);
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -0,0 +1,293 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::level::MovingPlatformData;
+use crate::{Player, PLAYER_RADIUS};
+
+// ===== PATH SETTINGS =====
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathMode {
+    Loop, // Back to the first waypoint from the last, round and round
+    #[default]
+    PingPong, // There and back again
+    Once, // Stops at the last waypoint
+}
+
+// Speed profile within each segment between two waypoints
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+// ===== PLATFORM KINDS =====
+// How a static platform behaves when the player is on it
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PlatformKind {
+    #[default]
+    Solid,
+    Crumbling { delay: f32, respawn: f32 }, // Falls `delay` seconds after first contact, back after `respawn`
+    Bouncy { restitution: f32 },
+    Ice { friction: f32 },
+    Conveyor { velocity: [f32; 3] }, // Belt speed added to anything standing on it
+    OneWay,                          // Solid from above, passable from below
+}
+
+// How quickly the player can change speed on ice, per second
+pub const ICE_CONTROL: f32 = 1.5;
+
+#[derive(Component, Clone, Debug)]
+pub struct CrumbleState {
+    pub home: Transform,
+    pub timer: Option<f32>, // Counting down to the fall once touched
+    pub fallen_for: Option<f32>,
+}
+
+impl CrumbleState {
+    pub fn new(home: Transform) -> Self {
+        Self {
+            home,
+            timer: None,
+            fallen_for: None,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct OneWayPlatform {
+    pub top: f32, // Height the player's feet must be above for it to be solid
+}
+
+// ===== MOVING PLATFORM =====
+#[derive(Component, Clone, Debug)]
+pub struct MovingPlatform {
+    pub waypoints: Vec<Vec3>, // At least two
+    pub mode: PathMode,
+    pub easing: Easing,
+    pub speed: f32,          // Units per second along the path
+    pub pause: f32,          // Seconds to wait at each end
+    pub rotate: Vec3,        // Euler angles (radians) reached at the end of the path
+    pub spin: Vec3,          // Constant spin in radians per second
+    pub start_on_step: bool, // Wait for the player to land before moving
+
+    // Runtime state
+    pub distance: f32,  // How far along the path
+    pub direction: f32, // 1.0 forward, -1.0 back (ping-pong)
+    pub pause_timer: f32,
+    pub active: bool,
+    pub spin_angles: Vec3,
+    pub velocity: Vec3, // Set each frame by `move_platforms`
+}
+
+impl MovingPlatform {
+    pub fn from_data(data: &MovingPlatformData) -> Self {
+        let waypoints = std::iter::once(data.start)
+            .chain(data.waypoints.iter().copied())
+            .chain(std::iter::once(data.end))
+            .map(Vec3::from)
+            .collect();
+
+        Self {
+            waypoints,
+            mode: data.mode,
+            easing: data.easing,
+            speed: data.speed,
+            pause: data.pause,
+            rotate: Vec3::from(data.rotate.map(f32::to_radians)),
+            spin: Vec3::from(data.spin.map(f32::to_radians)),
+            start_on_step: data.start_on_step,
+            distance: 0.0,
+            direction: 1.0,
+            pause_timer: 0.0,
+            active: !data.start_on_step,
+            spin_angles: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    // Segments in travel order; loops close back to the first waypoint
+    fn segments(&self) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+        let closing = (self.mode == PathMode::Loop)
+            .then(|| (*self.waypoints.last().unwrap_or(&Vec3::ZERO), self.waypoints[0]));
+        self.waypoints
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .chain(closing)
+    }
+
+    pub fn path_length(&self) -> f32 {
+        self.segments().map(|(a, b)| a.distance(b)).sum()
+    }
+
+    // Seconds for one full cycle, pauses included
+    pub fn cycle_seconds(&self) -> f32 {
+        let one_way = self.path_length() / self.speed.max(f32::EPSILON);
+        match self.mode {
+            PathMode::Loop | PathMode::Once => one_way + self.pause,
+            PathMode::PingPong => 2.0 * (one_way + self.pause),
+        }
+    }
+
+    // Eased position `distance` along the path
+    pub fn position_at(&self, distance: f32) -> Vec3 {
+        let mut remaining = distance.max(0.0);
+        let mut last = self.waypoints[0];
+        for (a, b) in self.segments() {
+            let length = a.distance(b);
+            if remaining <= length && length > 0.0 {
+                return a.lerp(b, self.easing.apply(remaining / length));
+            }
+            remaining -= length;
+            last = b;
+        }
+        last
+    }
+
+    pub fn rotation_at(&self, distance: f32) -> Quat {
+        let fraction = distance / self.path_length().max(f32::EPSILON);
+        let angles = self.rotate * fraction + self.spin_angles;
+        Quat::from_euler(EulerRot::XYZ, angles.x, angles.y, angles.z)
+    }
+
+    // Moves `distance` along the path, turning, wrapping or stopping at the ends
+    fn advance(&mut self, delta: f32) {
+        self.spin_angles += self.spin * delta;
+        if self.pause_timer > 0.0 {
+            self.pause_timer -= delta;
+            return;
+        }
+
+        let length = self.path_length();
+        self.distance += self.direction * self.speed * delta;
+
+        match self.mode {
+            PathMode::Loop if self.distance >= length => {
+                self.distance = if self.pause > 0.0 { 0.0 } else { self.distance - length };
+                self.pause_timer = self.pause;
+            }
+            PathMode::PingPong if self.distance >= length => {
+                self.distance = length;
+                self.direction = -1.0;
+                self.pause_timer = self.pause;
+            }
+            PathMode::PingPong if self.distance <= 0.0 => {
+                self.distance = 0.0;
+                self.direction = 1.0;
+                self.pause_timer = self.pause;
+            }
+            PathMode::Once if self.distance >= length => {
+                self.distance = length;
+                self.active = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+// ===== PLATFORM SYSTEMS =====
+// Platforms are driven through Rapier's next kinematic position so it sees their
+// velocity and pushes riders instead of letting them clip through. Runs in
+// `FixedUpdate` right before each physics step, so `delta` is that step.
+pub fn move_platforms(
+    mut platform_query: Query<(Entity, &mut MovingPlatform)>,
+    player_query: Query<&Player>,
+    mut rapier_context: ResMut<RapierContext>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, mut platform) in &mut platform_query {
+        // Step-triggered platforms wake up once the player lands on them
+        if platform.start_on_step
+            && platform.distance == 0.0
+            && player_query.iter().any(|player| player.ground_entity == Some(entity))
+        {
+            platform.active = true;
+        }
+
+        let Some(&handle) = rapier_context.entity2body().get(&entity) else {
+            continue; // Not in the physics world yet
+        };
+        let Some(body) = rapier_context.bodies.get_mut(handle) else {
+            continue;
+        };
+
+        let current = platform.position_at(platform.distance);
+        if platform.active {
+            platform.advance(delta);
+        }
+        let next = platform.position_at(platform.distance);
+
+        body.set_next_kinematic_translation(next.into());
+        body.set_next_kinematic_rotation(platform.rotation_at(platform.distance).into());
+        platform.velocity = if delta > 0.0 { (next - current) / delta } else { Vec3::ZERO };
+    }
+}
+
+// Crumbling platforms drop out from under the player, then reappear where they were
+pub fn crumble_platforms(
+    mut commands: Commands,
+    mut platform_query: Query<(Entity, &PlatformKind, &mut CrumbleState, &mut Transform)>,
+    player_query: Query<&Player>,
+    time: Res<Time>,
+) {
+    for (entity, kind, mut crumble, mut transform) in &mut platform_query {
+        let PlatformKind::Crumbling { delay, respawn } = *kind else {
+            continue;
+        };
+
+        if let Some(fallen_for) = crumble.fallen_for.as_mut() {
+            *fallen_for += time.delta_seconds();
+            if *fallen_for >= respawn {
+                *transform = crumble.home;
+                crumble.fallen_for = None;
+                commands.entity(entity).insert((RigidBody::Fixed, Velocity::zero()));
+            }
+        } else if let Some(timer) = crumble.timer.as_mut() {
+            *timer -= time.delta_seconds();
+            if *timer <= 0.0 {
+                crumble.timer = None;
+                crumble.fallen_for = Some(0.0);
+                commands.entity(entity).insert(RigidBody::Dynamic);
+            }
+        } else if player_query.iter().any(|player| player.ground_entity == Some(entity)) {
+            crumble.timer = Some(delay);
+        }
+    }
+}
+
+// One-way platforms only collide while the player is above their top
+pub fn update_one_way_platforms(
+    mut commands: Commands,
+    platform_query: Query<(Entity, &OneWayPlatform, Has<ColliderDisabled>)>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let feet = player_transform.translation.y - PLAYER_RADIUS;
+
+    for (entity, platform, disabled) in &platform_query {
+        let solid = feet >= platform.top - 0.05;
+        if solid && disabled {
+            commands.entity(entity).remove::<ColliderDisabled>();
+        } else if !solid && !disabled {
+            commands.entity(entity).insert(ColliderDisabled);
+        }
+    }
+}
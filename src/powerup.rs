@@ -0,0 +1,237 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::events::PowerUpCollected;
+use crate::{AppState, Player};
+
+pub const SPEED_BOOST_MULTIPLIER: f32 = 1.5;
+pub const LOW_GRAVITY_SCALE: f32 = 0.5;
+pub const SHIELD_INVULNERABILITY: f32 = 1.0; // Grace period after the shield absorbs a hit
+pub const MAX_EFFECT_SECONDS: f32 = 30.0; // Stacked durations stop growing here
+pub const MAX_SHIELD_CHARGES: u32 = 3;
+pub const POWER_UP_RADIUS: f32 = 0.4;
+
+// ===== POWER-UP PLUGIN =====
+// Pickups that grant the player timed effects, and the HUD listing what's active.
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_power_up_hud)
+            .add_systems(
+                Update,
+                (collect_power_ups, tick_effects, spin_power_ups).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, update_power_up_hud);
+    }
+}
+
+// ===== POWER-UP KINDS =====
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PowerUpKind {
+    DoubleJump, // One extra jump in the air
+    SpeedBoost, // Run `SPEED_BOOST_MULTIPLIER` times faster
+    Shield,     // Each charge absorbs one hazard hit
+    LowGravity, // Falls at `LOW_GRAVITY_SCALE` of normal gravity
+}
+
+impl PowerUpKind {
+    // Used when a level file doesn't give a duration
+    pub fn default_duration(self) -> f32 {
+        match self {
+            PowerUpKind::DoubleJump => 15.0,
+            PowerUpKind::SpeedBoost => 8.0,
+            PowerUpKind::Shield => 20.0,
+            PowerUpKind::LowGravity => 10.0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUpKind::DoubleJump => "🦘 Double Jump",
+            PowerUpKind::SpeedBoost => "⚡ Speed Boost",
+            PowerUpKind::Shield => "🛡️ Shield",
+            PowerUpKind::LowGravity => "🪶 Low Gravity",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            PowerUpKind::DoubleJump => Color::rgb(0.2, 1.0, 0.4),
+            PowerUpKind::SpeedBoost => Color::rgb(1.0, 0.9, 0.1),
+            PowerUpKind::Shield => Color::rgb(0.2, 0.6, 1.0),
+            PowerUpKind::LowGravity => Color::rgb(0.8, 0.4, 1.0),
+        }
+    }
+}
+
+// ===== COMPONENTS =====
+// A pickup in the level
+#[derive(Component)]
+pub struct PowerUp {
+    pub kind: PowerUpKind,
+    pub duration: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ActiveEffect {
+    pub kind: PowerUpKind,
+    pub remaining: f32,
+    pub charges: u32, // Shield hits left; unused by the other kinds
+}
+
+// Effects currently on the player. Picking up a kind that is already active adds
+// its duration to the time left (up to `MAX_EFFECT_SECONDS`); shields also gain a
+// charge, up to `MAX_SHIELD_CHARGES`. Different kinds run side by side.
+#[derive(Component, Default, Debug)]
+pub struct ActiveEffects {
+    pub effects: Vec<ActiveEffect>,
+    pub air_jump_used: bool, // Double jump spent since last leaving the ground
+}
+
+impl ActiveEffects {
+    pub fn add(&mut self, kind: PowerUpKind, duration: f32) {
+        match self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            Some(effect) => {
+                effect.remaining = (effect.remaining + duration).min(MAX_EFFECT_SECONDS);
+                effect.charges = (effect.charges + 1).min(MAX_SHIELD_CHARGES);
+            }
+            None => self.effects.push(ActiveEffect {
+                kind,
+                remaining: duration.min(MAX_EFFECT_SECONDS),
+                charges: 1,
+            }),
+        }
+    }
+
+    pub fn has(&self, kind: PowerUpKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.has(PowerUpKind::SpeedBoost) { SPEED_BOOST_MULTIPLIER } else { 1.0 }
+    }
+
+    pub fn gravity_scale(&self) -> f32 {
+        if self.has(PowerUpKind::LowGravity) { LOW_GRAVITY_SCALE } else { 1.0 }
+    }
+
+    // Spends a shield charge if there is one; true when the hit was absorbed
+    pub fn absorb_hit(&mut self) -> bool {
+        let Some(index) = self.effects.iter().position(|effect| effect.kind == PowerUpKind::Shield) else {
+            return false;
+        };
+        self.effects[index].charges -= 1;
+        if self.effects[index].charges == 0 {
+            self.effects.remove(index);
+        }
+        true
+    }
+
+    // Counts down every effect and drops the expired ones
+    pub fn tick(&mut self, delta: f32) {
+        for effect in &mut self.effects {
+            effect.remaining -= delta;
+        }
+        self.effects.retain(|effect| effect.remaining > 0.0);
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+        self.air_jump_used = false;
+    }
+}
+
+#[derive(Component)]
+pub struct PowerUpHud;
+
+// ===== POWER-UP SYSTEMS =====
+fn collect_power_ups(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut player_query: Query<(Entity, &mut ActiveEffects), With<Player>>,
+    power_up_query: Query<&PowerUp>,
+    mut power_up_events: EventWriter<PowerUpCollected>,
+) {
+    let Ok((player_entity, mut effects)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(first, second, _) = *event else {
+            continue;
+        };
+        let other = if first == player_entity {
+            second
+        } else if second == player_entity {
+            first
+        } else {
+            continue;
+        };
+
+        if let Ok(power_up) = power_up_query.get(other) {
+            effects.add(power_up.kind, power_up.duration);
+            commands.entity(other).despawn();
+            power_up_events.send(PowerUpCollected {
+                kind: power_up.kind,
+                duration: power_up.duration,
+            });
+        }
+    }
+}
+
+fn tick_effects(mut player_query: Query<(&mut ActiveEffects, &mut GravityScale)>, time: Res<Time>) {
+    for (mut effects, mut gravity_scale) in &mut player_query {
+        effects.tick(time.delta_seconds());
+        gravity_scale.0 = effects.gravity_scale();
+    }
+}
+
+fn spin_power_ups(mut power_up_query: Query<&mut Transform, With<PowerUp>>, time: Res<Time>) {
+    for mut transform in &mut power_up_query {
+        transform.rotate_y(time.delta_seconds() * 2.0);
+    }
+}
+
+fn spawn_power_up_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 26.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(110.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        PowerUpHud,
+    ));
+}
+
+// One line per active effect with the seconds left
+fn update_power_up_hud(
+    player_query: Query<&ActiveEffects, With<Player>>,
+    mut hud_query: Query<&mut Text, With<PowerUpHud>>,
+) {
+    let Ok(effects) = player_query.get_single() else {
+        return;
+    };
+
+    for mut text in &mut hud_query {
+        text.sections[0].value = effects
+            .effects
+            .iter()
+            .map(|effect| match effect.kind {
+                PowerUpKind::Shield => format!("{} x{} {:.1}s", effect.kind.label(), effect.charges, effect.remaining),
+                kind => format!("{} {:.1}s", kind.label(), effect.remaining),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+}
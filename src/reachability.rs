@@ -0,0 +1,493 @@
+use bevy::prelude::*;
+
+use crate::generator::{GROUND_CENTER, GROUND_SIZE};
+use crate::level::{LevelData, MovingPlatformData};
+use crate::platform::{MovingPlatform, PlatformKind};
+use crate::{COLLECT_RADIUS, PLAYER_RADIUS};
+
+// Fraction of the theoretical jump height and distance a gap may use.
+// Leaves room for reaction time, air steering and landing on a small top.
+pub const REACH_SAFETY: f32 = 0.8;
+
+// ===== JUMP ARC =====
+// Ballistic jump of the player: constant horizontal run speed, instant vertical
+// launch velocity and the physics world's gravity.
+#[derive(Clone, Copy, Debug)]
+pub struct JumpArc {
+    pub speed: f32,
+    pub jump_force: f32,
+    pub gravity: f32, // Magnitude, positive down
+    pub safety: f32,  // Share of the arc a gap may use, `REACH_SAFETY` by default
+}
+
+impl Default for JumpArc {
+    // Matches the player spawned in `setup_game` under Rapier's default gravity
+    fn default() -> Self {
+        Self {
+            speed: 8.0,
+            jump_force: 12.0,
+            gravity: 9.81,
+            safety: REACH_SAFETY,
+        }
+    }
+}
+
+impl JumpArc {
+    // Apex height above the take-off surface
+    pub fn max_height(&self) -> f32 {
+        self.jump_force * self.jump_force / (2.0 * self.gravity)
+    }
+
+    // Airtime until the arc comes back down to `rise` above take-off, or `None` if it never gets that high
+    pub fn airtime(&self, rise: f32) -> Option<f32> {
+        let discriminant = self.jump_force * self.jump_force - 2.0 * self.gravity * rise;
+        if discriminant < 0.0 {
+            return None;
+        }
+        Some((self.jump_force + discriminant.sqrt()) / self.gravity)
+    }
+
+    // Horizontal distance covered before landing `rise` above take-off
+    pub fn max_reach(&self, rise: f32) -> Option<f32> {
+        self.airtime(rise).map(|airtime| airtime * self.speed)
+    }
+
+    // Off a bouncy platform: a jump in place comes back down at `jump_force` and
+    // bounces up at `restitution` times that, so the stronger of the two launches.
+    // Only one bounce is counted, however high repeated ones would climb.
+    pub fn bounced(&self, restitution: f32) -> Self {
+        Self {
+            jump_force: self.jump_force * restitution.max(1.0),
+            ..*self
+        }
+    }
+
+    // Whether a gap of `horizontal` edge-to-edge distance and `rise` between
+    // surfaces is inside the safe part of the arc
+    pub fn can_clear(&self, horizontal: f32, rise: f32) -> bool {
+        if rise > self.max_height() * self.safety {
+            return false;
+        }
+        self.max_reach(rise)
+            .is_some_and(|reach| horizontal <= reach * self.safety)
+    }
+}
+
+// ===== BOX GEOMETRY =====
+// Axis-aligned box given by its center and full size, as in level files
+#[derive(Clone, Copy, Debug)]
+pub struct Surface {
+    pub center: Vec3,
+    pub size: Vec3,
+}
+
+impl Surface {
+    pub fn new(center: [f32; 3], size: [f32; 3]) -> Self {
+        Self {
+            center: Vec3::from(center),
+            size: Vec3::from(size),
+        }
+    }
+
+    pub fn top(&self) -> f32 {
+        self.center.y + self.size.y / 2.0
+    }
+
+    // Shortest XZ distance between the two footprints; zero when they overlap
+    pub fn edge_distance(&self, other: &Surface) -> f32 {
+        let gap_x = ((self.center.x - other.center.x).abs() - (self.size.x + other.size.x) / 2.0).max(0.0);
+        let gap_z = ((self.center.z - other.center.z).abs() - (self.size.z + other.size.z) / 2.0).max(0.0);
+        Vec2::new(gap_x, gap_z).length()
+    }
+
+    // Whether this box hangs over all of `other`'s top, so there is no way around it
+    // from there
+    pub fn covers(&self, other: &Surface) -> bool {
+        self.center.y - self.size.y / 2.0 >= other.top()
+            && (self.center.x - other.center.x).abs() + other.size.x / 2.0 <= self.size.x / 2.0
+            && (self.center.z - other.center.z).abs() + other.size.z / 2.0 <= self.size.z / 2.0
+    }
+
+    // Shortest XZ distance from a point to the footprint
+    pub fn distance_to_point(&self, point: Vec3) -> f32 {
+        let gap_x = ((self.center.x - point.x).abs() - self.size.x / 2.0).max(0.0);
+        let gap_z = ((self.center.z - point.z).abs() - self.size.z / 2.0).max(0.0);
+        Vec2::new(gap_x, gap_z).length()
+    }
+}
+
+// Can the player standing on `from` jump onto the top of `to`?
+pub fn can_jump(arc: &JumpArc, from: &Surface, to: &Surface) -> bool {
+    arc.can_clear(from.edge_distance(to), to.top() - from.top())
+}
+
+// ===== LEVEL ANALYSIS =====
+// Positions sampled along each moving platform's cycle
+const MOTION_SAMPLES: usize = 32;
+
+// Something the player can stand on: one position for static platforms, a sampled
+// cycle for moving ones
+#[derive(Clone, Debug)]
+pub struct Standable {
+    pub label: String,
+    pub positions: Vec<Surface>,
+    pub cycle_seconds: Option<f32>,
+    pub kind: PlatformKind, // Moving platforms are always `Solid`
+}
+
+impl Standable {
+    fn fixed(label: String, surface: Surface, kind: PlatformKind) -> Self {
+        Self {
+            label,
+            positions: vec![surface],
+            cycle_seconds: None,
+            kind,
+        }
+    }
+
+    // The arc of jumps taken from here
+    fn jump_arc(&self, arc: &JumpArc) -> JumpArc {
+        match self.kind {
+            PlatformKind::Bouncy { restitution } => arc.bounced(restitution),
+            _ => *arc,
+        }
+    }
+
+    // Can the player on `origin` jump onto this standable at `target`? Solid
+    // platforms can't be jumped through from right underneath; one-way ones can.
+    fn can_board(&self, arc: &JumpArc, origin: &Surface, target: &Surface) -> bool {
+        can_jump(arc, origin, target) && (self.kind == PlatformKind::OneWay || !target.covers(origin))
+    }
+
+    // Samples the same path `move_platforms` follows; rotation is ignored
+    fn moving(label: String, data: &MovingPlatformData) -> Self {
+        let platform = MovingPlatform::from_data(data);
+        let length = platform.path_length();
+
+        let positions = (0..MOTION_SAMPLES)
+            .map(|i| Surface {
+                center: platform.position_at(length * i as f32 / (MOTION_SAMPLES - 1) as f32),
+                size: Vec3::from(data.size),
+            })
+            .collect();
+
+        Self {
+            label,
+            positions,
+            cycle_seconds: Some(platform.cycle_seconds()),
+            kind: PlatformKind::Solid,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct StandableReach {
+    pub label: String,
+    pub reachable: bool,
+    // Share of a moving platform's cycle during which it can be boarded
+    pub timing_window: Option<f32>,
+    pub cycle_seconds: Option<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CollectibleReach {
+    pub position: Vec3,
+    pub value: u32,
+    pub reachable: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReachabilityReport {
+    pub standables: Vec<StandableReach>,
+    pub collectibles: Vec<CollectibleReach>,
+}
+
+impl ReachabilityReport {
+    pub fn unreachable_collectibles(&self) -> impl Iterator<Item = &CollectibleReach> {
+        self.collectibles.iter().filter(|collectible| !collectible.reachable)
+    }
+}
+
+// Flood-fills jumps from the surface under the spawn point. Moving platforms are
+// sampled over their cycle; since the player can wait for the right moment, any
+// boardable sample counts. Platform motion during the jump itself is ignored.
+// Bouncy platforms launch higher, one-way ones can be jumped up through and the
+// other kinds stand like solid ones; crumbling ones are assumed to hold long enough.
+pub fn analyze_level(data: &LevelData, arc: &JumpArc) -> ReachabilityReport {
+    let ground = Surface::new(GROUND_CENTER, GROUND_SIZE);
+    let mut standables = vec![Standable::fixed("Ground".to_string(), ground, PlatformKind::Solid)];
+    standables.extend(data.platforms.iter().enumerate().map(|(i, platform)| {
+        let surface = Surface::new(platform.position, platform.size);
+        Standable::fixed(format!("Platform {}", i + 1), surface, platform.kind)
+    }));
+    standables.extend(
+        data.moving_platforms
+            .iter()
+            .enumerate()
+            .map(|(i, platform)| Standable::moving(format!("Moving platform {}", i + 1), platform)),
+    );
+
+    let start = spawn_surface(&standables, Vec3::from(data.spawn_point));
+    let mut reachable = vec![false; standables.len()];
+    let mut windows: Vec<Option<f32>> = vec![None; standables.len()];
+    reachable[start] = true;
+
+    let mut frontier = vec![start];
+    while let Some(from) = frontier.pop() {
+        let from_arc = standables[from].jump_arc(arc);
+        for to in 0..standables.len() {
+            let boardable = standables[to]
+                .positions
+                .iter()
+                .filter(|target| {
+                    standables[from]
+                        .positions
+                        .iter()
+                        .any(|origin| standables[to].can_board(&from_arc, origin, target))
+                })
+                .count();
+            if boardable == 0 {
+                continue;
+            }
+
+            if standables[to].cycle_seconds.is_some() {
+                let window = boardable as f32 / standables[to].positions.len() as f32;
+                windows[to] = Some(windows[to].map_or(window, |best: f32| best.max(window)));
+            }
+            if !reachable[to] {
+                reachable[to] = true;
+                frontier.push(to);
+            }
+        }
+    }
+
+    let collectibles = data
+        .collectibles
+        .iter()
+        .map(|collectible| {
+            let position = Vec3::from(collectible.position);
+            let reachable = standables
+                .iter()
+                .zip(&reachable)
+                .filter(|(_, reachable)| **reachable)
+                .any(|(standable, _)| {
+                    let arc = standable.jump_arc(arc);
+                    standable
+                        .positions
+                        .iter()
+                        .any(|surface| can_collect_from(&arc, surface, position))
+                });
+            CollectibleReach {
+                position,
+                value: collectible.value,
+                reachable,
+            }
+        })
+        .collect();
+
+    ReachabilityReport {
+        standables: standables
+            .into_iter()
+            .zip(reachable)
+            .zip(windows)
+            .map(|((standable, reachable), timing_window)| StandableReach {
+                label: standable.label,
+                reachable,
+                timing_window,
+                cycle_seconds: standable.cycle_seconds,
+            })
+            .collect(),
+        collectibles,
+    }
+}
+
+// Highest surface under the spawn point; the ground if nothing else is
+fn spawn_surface(standables: &[Standable], spawn: Vec3) -> usize {
+    standables
+        .iter()
+        .enumerate()
+        .filter_map(|(i, standable)| {
+            let surface = standable.positions.first()?;
+            (surface.distance_to_point(spawn) == 0.0 && surface.top() <= spawn.y).then_some((i, surface.top()))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+// Within `COLLECT_RADIUS` of the player anywhere between standing on `surface`
+// and the apex of a jump straight up from it
+fn can_collect_from(arc: &JumpArc, surface: &Surface, position: Vec3) -> bool {
+    let lowest = surface.top() + PLAYER_RADIUS;
+    let highest = lowest + arc.max_height();
+    let vertical = if position.y < lowest {
+        lowest - position.y
+    } else {
+        (position.y - highest).max(0.0)
+    };
+    Vec2::new(surface.distance_to_point(position), vertical).length() < COLLECT_RADIUS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::{CollectibleData, LightingData, PlatformData};
+    use crate::platform::PlatformKind;
+
+    fn arc() -> JumpArc {
+        JumpArc {
+            speed: 10.0,
+            jump_force: 10.0,
+            gravity: 10.0,
+            safety: 1.0,
+        }
+    }
+
+    fn platform(position: [f32; 3]) -> PlatformData {
+        PlatformData {
+            position,
+            size: [2.0, 1.0, 2.0],
+            color: [0.5; 3],
+            emissive: None,
+            kind: PlatformKind::Solid,
+        }
+    }
+
+    fn collectible(position: [f32; 3]) -> CollectibleData {
+        CollectibleData {
+            position,
+            value: 10,
+            color: [1.0; 3],
+            emissive: None,
+        }
+    }
+
+    fn level(platforms: Vec<PlatformData>, collectibles: Vec<CollectibleData>) -> LevelData {
+        LevelData {
+            name: "Test".to_string(),
+            spawn_point: [0.0, 2.0, 0.0],
+            lighting: LightingData {
+                sun_illuminance: 0.0,
+                sun_position: [0.0; 3],
+                point_lights: Vec::new(),
+            },
+            platforms,
+            moving_platforms: Vec::new(),
+            collectibles,
+            hazards: Vec::new(),
+            checkpoints: Vec::new(),
+            power_ups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn max_reach_follows_the_arc() {
+        let arc = arc();
+        assert_eq!(arc.max_height(), 5.0);
+        // Up and back down takes 2 s at 10 units/s
+        assert_eq!(arc.max_reach(0.0), Some(20.0));
+        assert_eq!(arc.max_reach(5.0), Some(10.0)); // Landing right at the apex
+        assert!(arc.max_reach(-5.0).unwrap() > 20.0);
+        assert_eq!(arc.max_reach(5.1), None);
+
+        assert!(arc.can_clear(20.0, 0.0));
+        assert!(!arc.can_clear(20.1, 0.0));
+        let safe = JumpArc { safety: 0.5, ..arc };
+        assert!(!safe.can_clear(1.0, 3.0)); // Above half the apex
+        assert!(safe.can_clear(10.0, 0.0));
+        assert!(!safe.can_clear(10.1, 0.0));
+    }
+
+    #[test]
+    fn jumps_are_measured_between_edges() {
+        let arc = arc();
+        let from = Surface::new([0.0, 0.0, 0.0], [2.0, 1.0, 2.0]);
+        let near = Surface::new([20.0, 0.0, 0.0], [2.0, 1.0, 2.0]); // 18 apart
+        let far = Surface::new([23.0, 0.0, 0.0], [2.0, 1.0, 2.0]); // 21 apart
+        let high = Surface::new([3.0, 6.0, 0.0], [2.0, 1.0, 2.0]);
+        assert_eq!(from.edge_distance(&near), 18.0);
+        assert!(can_jump(&arc, &from, &near));
+        assert!(!can_jump(&arc, &from, &far));
+        assert!(!can_jump(&arc, &from, &high));
+    }
+
+    #[test]
+    fn flood_fill_follows_chains_of_jumps() {
+        // Ground top at 0: 4 up, then 4 more, then a platform too high to reach from either
+        let data = level(
+            vec![
+                platform([16.0, 3.5, 0.0]),
+                platform([16.0, 7.5, 6.0]),
+                platform([16.0, 30.0, 6.0]),
+            ],
+            vec![collectible([16.0, 9.0, 6.0]), collectible([16.0, 32.0, 6.0])],
+        );
+        let report = analyze_level(&data, &arc());
+
+        let reachable: Vec<bool> = report.standables.iter().map(|standable| standable.reachable).collect();
+        assert_eq!(reachable, vec![true, true, true, false]);
+        assert_eq!(report.standables[3].label, "Platform 3");
+        let unreachable: Vec<Vec3> = report
+            .unreachable_collectibles()
+            .map(|collectible| collectible.position)
+            .collect();
+        assert_eq!(unreachable, vec![Vec3::new(16.0, 32.0, 6.0)]);
+    }
+
+    #[test]
+    fn flood_fill_starts_from_the_surface_under_the_spawn() {
+        // A platform high above the ground and a step up from it
+        let mut data = level(
+            vec![platform([0.0, 50.0, 0.0]), platform([0.0, 53.5, 4.0])],
+            vec![collectible([0.0, 56.0, 4.0])],
+        );
+        let from_ground = analyze_level(&data, &arc());
+        assert!(from_ground.standables.iter().skip(1).all(|standable| !standable.reachable));
+        assert_eq!(from_ground.unreachable_collectibles().count(), 1);
+
+        // Spawned on the high platform, the step is a jump away and the ground a drop
+        data.spawn_point = [0.0, 52.0, 0.0];
+        let from_platform = analyze_level(&data, &arc());
+        assert!(from_platform.standables.iter().all(|standable| standable.reachable));
+        assert_eq!(from_platform.unreachable_collectibles().count(), 0);
+    }
+
+    #[test]
+    fn bouncy_platforms_launch_higher() {
+        // A pad by the spawn and a ledge 8 above it, past the apex of a plain jump
+        let mut data = level(
+            vec![platform([0.0, 0.5, 6.0]), platform([0.0, 8.5, 10.0])],
+            vec![collectible([0.0, 10.0, 10.0])],
+        );
+        assert!(!analyze_level(&data, &arc()).standables[2].reachable);
+
+        data.platforms[0].kind = PlatformKind::Bouncy { restitution: 1.5 };
+        let report = analyze_level(&data, &arc());
+        assert!(report.standables.iter().all(|standable| standable.reachable));
+        assert_eq!(report.unreachable_collectibles().count(), 0);
+    }
+
+    #[test]
+    fn only_one_way_platforms_can_be_jumped_through() {
+        // A step off the ground and a wide platform hanging right over it, too high
+        // to jump to from the ground
+        let mut data = level(
+            vec![
+                platform([16.0, 0.5, 0.0]),
+                PlatformData {
+                    size: [6.0, 1.0, 6.0],
+                    ..platform([16.0, 5.0, 0.0])
+                },
+            ],
+            vec![collectible([16.0, 8.0, 0.0])],
+        );
+        let report = analyze_level(&data, &arc());
+        assert!(report.standables[1].reachable);
+        assert!(!report.standables[2].reachable);
+        assert_eq!(report.unreachable_collectibles().count(), 1);
+
+        data.platforms[1].kind = PlatformKind::OneWay;
+        let report = analyze_level(&data, &arc());
+        assert!(report.standables.iter().all(|standable| standable.reachable));
+        assert_eq!(report.unreachable_collectibles().count(), 0);
+    }
+}
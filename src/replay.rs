@@ -0,0 +1,403 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use thiserror::Error;
+
+use crate::camera::{orbit_camera, OrbitCamera};
+use crate::controls::{Action, ActionAxes, ActionFrame, ActionOverride, ActionSystem};
+use crate::difficulty::Difficulty;
+use crate::level::ActiveLevel;
+use crate::{AppState, GameState, RunSeed};
+
+const REPLAY_MAGIC: &[u8; 4] = b"EXRP";
+const REPLAY_VERSION: u8 = 5;
+const HEADER_LEN: usize = 4 + 1 + 8 + 4 + 1 + 4;
+const FRAME_LEN: usize = 8 + 2 + 2 + 4 + 4 + 4;
+
+// Menu keys read straight from the keyboard, in bitmask order. Gameplay input is
+// recorded as actions, so replays don't depend on the recorder's bindings.
+pub const RECORDED_KEYS: [KeyCode; 5] = [KeyCode::Escape, KeyCode::Q, KeyCode::Return, KeyCode::L, KeyCode::O];
+
+// ===== REPLAY FILE =====
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReplayFrame {
+    pub delta_nanos: u64, // Exact, so playback steps physics the same number of times
+    pub keys: u16, // Bit i set = RECORDED_KEYS[i] held this frame
+    pub actions: ActionFrame,
+    pub camera_yaw: f32, // Movement is camera-relative, so the view is part of the input
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub level: u32,
+    pub difficulty: Difficulty,
+    pub frames: Vec<ReplayFrame>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Could not access replay file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Not a replay file")]
+    BadMagic,
+    #[error("Unsupported replay version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Replay file is truncated")]
+    Truncated,
+    #[error("Unknown difficulty {0} in replay")]
+    UnknownDifficulty(u8),
+}
+
+impl Replay {
+    // Little-endian header (magic, version, seed, level, difficulty, frame count) followed by
+    // one `u64` delta in nanoseconds, `u16` key mask, `u16` action mask, two `f32` movement
+    // axes and `f32` camera yaw per frame
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.frames.len() * FRAME_LEN);
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.push(REPLAY_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.level.to_le_bytes());
+        bytes.push(self.difficulty.index());
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for frame in &self.frames {
+            bytes.extend_from_slice(&frame.delta_nanos.to_le_bytes());
+            bytes.extend_from_slice(&frame.keys.to_le_bytes());
+            bytes.extend_from_slice(&frame.actions.pressed.to_le_bytes());
+            bytes.extend_from_slice(&frame.actions.movement.x.to_le_bytes());
+            bytes.extend_from_slice(&frame.actions.movement.y.to_le_bytes());
+            bytes.extend_from_slice(&frame.camera_yaw.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(ReplayError::Truncated);
+        }
+        if &bytes[0..4] != REPLAY_MAGIC {
+            return Err(ReplayError::BadMagic);
+        }
+        if bytes[4] != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(bytes[4]));
+        }
+
+        let seed = u64::from_le_bytes(bytes[5..13].try_into().unwrap());
+        let level = u32::from_le_bytes(bytes[13..17].try_into().unwrap());
+        let difficulty = Difficulty::from_index(bytes[17]).ok_or(ReplayError::UnknownDifficulty(bytes[17]))?;
+        let frame_count = u32::from_le_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        let body = &bytes[HEADER_LEN..];
+        if body.len() < frame_count * FRAME_LEN {
+            return Err(ReplayError::Truncated);
+        }
+
+        let frames = body
+            .chunks_exact(FRAME_LEN)
+            .take(frame_count)
+            .map(|chunk| ReplayFrame {
+                delta_nanos: u64::from_le_bytes(chunk[0..8].try_into().unwrap()),
+                keys: u16::from_le_bytes(chunk[8..10].try_into().unwrap()),
+                actions: ActionFrame {
+                    pressed: u16::from_le_bytes(chunk[10..12].try_into().unwrap()),
+                    movement: Vec2::new(
+                        f32::from_le_bytes(chunk[12..16].try_into().unwrap()),
+                        f32::from_le_bytes(chunk[16..20].try_into().unwrap()),
+                    ),
+                },
+                camera_yaw: f32::from_le_bytes(chunk[20..24].try_into().unwrap()),
+            })
+            .collect();
+
+        Ok(Self {
+            seed,
+            level,
+            difficulty,
+            frames,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+pub fn keys_to_mask(keyboard_input: &Input<KeyCode>) -> u16 {
+    RECORDED_KEYS
+        .iter()
+        .enumerate()
+        .filter(|(_, key)| keyboard_input.pressed(**key))
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+// ===== REPLAY PLUGIN =====
+// Add with `ReplayMode::Record` or `ReplayMode::Playback` to capture or reproduce a run.
+// Frames are counted from the first frame the player is in control of a loaded level.
+pub enum ReplayMode {
+    Record(PathBuf),
+    Playback(Replay),
+}
+
+pub struct ReplayPlugin {
+    pub mode: ReplayMode,
+}
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        match &self.mode {
+            ReplayMode::Record(path) => {
+                app.insert_resource(ReplayRecorder {
+                    path: path.clone(),
+                    replay: Replay::default(),
+                    started: false,
+                })
+                .add_systems(Startup, start_recording)
+                .add_systems(PreUpdate, record_frame.after(InputSystem).after(orbit_camera))
+                .add_systems(Last, (arm_recorder, save_recording_on_exit));
+            }
+            ReplayMode::Playback(replay) => {
+                app.insert_resource(RunSeed(replay.seed))
+                    .insert_resource(ReplayPlayer {
+                        replay: replay.clone(),
+                        cursor: 0,
+                        started: false,
+                    })
+                    .add_systems(Startup, start_playback)
+                    .add_systems(First, apply_replay_timing.before(TimeSystem))
+                    .add_systems(
+                        PreUpdate,
+                        (
+                            apply_replay_input.after(InputSystem).before(ActionSystem),
+                            apply_replay_camera.after(orbit_camera),
+                        ),
+                    )
+                    .add_systems(Last, (advance_playback, arm_player).chain());
+            }
+        }
+    }
+}
+
+fn run_in_progress(state: &State<AppState>, active_level: &ActiveLevel) -> bool {
+    *state.get() == AppState::Playing && active_level.pending.is_none()
+}
+
+// ===== RECORDING =====
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub replay: Replay,
+    pub started: bool,
+}
+
+fn start_recording(mut recorder: ResMut<ReplayRecorder>, seed: Res<RunSeed>, game_state: Res<GameState>) {
+    recorder.replay.seed = seed.0;
+    recorder.replay.level = game_state.level;
+    info!("⏺️ Recording replay to {}", recorder.path.display());
+}
+
+// The difficulty can still change on the title menu, so it's taken when the run starts
+fn arm_recorder(
+    mut recorder: ResMut<ReplayRecorder>,
+    state: Res<State<AppState>>,
+    active_level: Res<ActiveLevel>,
+    difficulty: Res<Difficulty>,
+) {
+    if !recorder.started && run_in_progress(&state, &active_level) {
+        recorder.started = true;
+        recorder.replay.difficulty = *difficulty;
+    }
+}
+
+fn record_frame(
+    mut recorder: ResMut<ReplayRecorder>,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<Input<Action>>,
+    action_axes: Res<ActionAxes>,
+    camera_query: Query<&OrbitCamera>,
+) {
+    if recorder.started {
+        let frame = ReplayFrame {
+            delta_nanos: time.delta().as_nanos() as u64,
+            keys: keys_to_mask(&keyboard_input),
+            actions: ActionFrame::capture(&actions, &action_axes),
+            camera_yaw: camera_query.get_single().map_or(0.0, |orbit| orbit.yaw),
+        };
+        recorder.replay.frames.push(frame);
+    }
+}
+
+fn save_recording_on_exit(recorder: Res<ReplayRecorder>, mut exit_events: EventReader<AppExit>) {
+    if exit_events.read().next().is_some() {
+        match recorder.replay.save(&recorder.path) {
+            Ok(()) => info!(
+                "💾 Saved {} replay frames to {}",
+                recorder.replay.frames.len(),
+                recorder.path.display()
+            ),
+            Err(err) => error!("Could not save replay {}: {}", recorder.path.display(), err),
+        }
+    }
+}
+
+// ===== PLAYBACK =====
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub cursor: usize,
+    pub started: bool,
+}
+
+impl ReplayPlayer {
+    fn current_frame(&self) -> Option<ReplayFrame> {
+        if self.started {
+            self.replay.frames.get(self.cursor).copied()
+        } else {
+            None
+        }
+    }
+}
+
+fn start_playback(
+    player: Res<ReplayPlayer>,
+    mut game_state: ResMut<GameState>,
+    mut active_level: ResMut<ActiveLevel>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    game_state.level = player.replay.level;
+    active_level.pending = Some(player.replay.level);
+    *difficulty = player.replay.difficulty;
+    info!(
+        "▶️ Playing back {} frames (seed {}, level {}, {})",
+        player.replay.frames.len(),
+        player.replay.seed,
+        player.replay.level,
+        player.replay.difficulty.name()
+    );
+}
+
+fn arm_player(mut player: ResMut<ReplayPlayer>, state: Res<State<AppState>>, active_level: Res<ActiveLevel>) {
+    if !player.started && run_in_progress(&state, &active_level) {
+        player.started = true;
+    }
+}
+
+// Feed the recorded frame time so timers and physics see identical deltas
+fn apply_replay_timing(player: Res<ReplayPlayer>, mut time_strategy: ResMut<TimeUpdateStrategy>) {
+    if let Some(frame) = player.current_frame() {
+        *time_strategy = TimeUpdateStrategy::ManualDuration(Duration::from_nanos(frame.delta_nanos));
+    }
+}
+
+fn apply_replay_input(
+    player: Res<ReplayPlayer>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut action_override: ResMut<ActionOverride>,
+) {
+    let Some(frame) = player.current_frame() else {
+        return;
+    };
+
+    // Recorded keys fully replace the live keyboard
+    for (i, key) in RECORDED_KEYS.iter().enumerate() {
+        if frame.keys & (1 << i) != 0 {
+            keyboard_input.press(*key);
+        } else {
+            keyboard_input.release(*key);
+        }
+    }
+    // Recorded actions fully replace the bound devices
+    action_override.0 = Some(frame.actions);
+}
+
+// Turn the camera as it was so camera-relative movement matches
+fn apply_replay_camera(player: Res<ReplayPlayer>, mut camera_query: Query<&mut OrbitCamera>) {
+    if let Some(frame) = player.current_frame() {
+        for mut orbit in &mut camera_query {
+            orbit.yaw = frame.camera_yaw;
+        }
+    }
+}
+
+// Moves on once the frame has been fed to every system
+fn advance_playback(mut player: ResMut<ReplayPlayer>) {
+    if player.current_frame().is_none() {
+        return;
+    }
+    player.cursor += 1;
+    if player.cursor == player.replay.frames.len() {
+        info!("⏹️ Replay finished");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay() -> Replay {
+        Replay {
+            seed: 0xDEAD_BEEF_1234,
+            level: 3,
+            difficulty: Difficulty::Nightmare,
+            frames: (0..10)
+                .map(|i| ReplayFrame {
+                    delta_nanos: 16_666_667 + i as u64,
+                    keys: i,
+                    actions: ActionFrame {
+                        pressed: i * 3,
+                        movement: Vec2::new(0.5, -1.0),
+                    },
+                    camera_yaw: i as f32 * 0.1,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let replay = replay();
+        let bytes = replay.to_bytes();
+        assert_eq!(bytes.len(), HEADER_LEN + 10 * FRAME_LEN);
+        assert_eq!(Replay::from_bytes(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let bytes = replay().to_bytes();
+        for len in [0, HEADER_LEN - 1, bytes.len() - 1] {
+            assert!(matches!(Replay::from_bytes(&bytes[..len]), Err(ReplayError::Truncated)), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_other_versions_and_files() {
+        let mut bytes = replay().to_bytes();
+        bytes[4] = REPLAY_VERSION - 1;
+        assert!(matches!(
+            Replay::from_bytes(&bytes),
+            Err(ReplayError::UnsupportedVersion(version)) if version == REPLAY_VERSION - 1
+        ));
+
+        bytes[0] = b'X';
+        assert!(matches!(Replay::from_bytes(&bytes), Err(ReplayError::BadMagic)));
+    }
+
+    #[test]
+    fn rejects_unknown_difficulties() {
+        let mut bytes = replay().to_bytes();
+        bytes[17] = 200;
+        assert!(matches!(Replay::from_bytes(&bytes), Err(ReplayError::UnknownDifficulty(200))));
+    }
+}
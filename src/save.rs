@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::editor::{not_play_testing, LevelEditor};
+use crate::events::{LeaderboardRank, NewBestTime};
+use crate::speedrun::SplitRecord;
+use crate::{AppState, GameState};
+
+pub const LEADERBOARD_SIZE: usize = 10;
+// Longest player name the options screen accepts
+pub const PLAYER_NAME_MAX_CHARS: usize = 16;
+// Used when there is no login name to start from, or the name is cleared
+pub const DEFAULT_PLAYER_NAME: &str = "Player";
+
+// ===== SAVE PLUGIN =====
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        // Headless runs insert `ProfilePath(None)` first to keep tests off the disk
+        if !app.world.contains_resource::<ProfilePath>() {
+            app.insert_resource(ProfilePath(default_profile_path()));
+        }
+
+        app.init_resource::<Profile>()
+            .init_resource::<RunClock>()
+            .add_systems(Startup, load_profile)
+            .add_systems(Update, tick_run_clock.run_if(in_state(AppState::Playing)))
+            .add_systems(OnEnter(AppState::LevelTransition), record_level_complete)
+            .add_systems(OnEnter(AppState::GameOver), record_game_over.run_if(not_play_testing));
+    }
+}
+
+// ===== PROFILE DATA =====
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    pub level: u32,
+    pub time_seconds: f32,
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct Profile {
+    pub player_name: String, // Shown on the leaderboard; set on the options screen
+    pub highest_level: u32,
+    pub best_level_times: BTreeMap<u32, f32>, // Level number -> fastest clear in seconds
+    pub leaderboard: Vec<LeaderboardEntry>,
+    #[serde(default)]
+    pub splits: BTreeMap<Difficulty, SplitRecord>, // Personal best splits per difficulty
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        let player_name = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| DEFAULT_PLAYER_NAME.to_string());
+
+        Self {
+            player_name,
+            highest_level: 1,
+            best_level_times: BTreeMap::new(),
+            leaderboard: Vec::new(),
+            splits: BTreeMap::new(),
+        }
+    }
+}
+
+impl Profile {
+    // Insert a finished run, keeping the best `LEADERBOARD_SIZE` by score then time.
+    // Returns the 1-based rank if the run made the board.
+    pub fn submit_run(&mut self, score: u32, level: u32, time_seconds: f32) -> Option<usize> {
+        let entry = LeaderboardEntry {
+            name: self.player_name.clone(),
+            score,
+            level,
+            time_seconds,
+        };
+        let rank = self
+            .leaderboard
+            .iter()
+            .position(|other| score > other.score || (score == other.score && time_seconds < other.time_seconds))
+            .unwrap_or(self.leaderboard.len());
+
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.leaderboard.insert(rank, entry);
+        self.leaderboard.truncate(LEADERBOARD_SIZE);
+        Some(rank + 1)
+    }
+
+    // Returns true if this is a new best time for the level
+    pub fn record_level_time(&mut self, level: u32, time_seconds: f32) -> bool {
+        let best = self.best_level_times.entry(level).or_insert(f32::MAX);
+        let improved = time_seconds < *best;
+        if improved {
+            *best = time_seconds;
+        }
+        improved
+    }
+}
+
+// ===== PERSISTENCE =====
+#[derive(Resource, Clone, Debug)]
+pub struct ProfilePath(pub Option<PathBuf>);
+
+// `$XDG_DATA_HOME/extreme-3d-platformer/profile.ron`, or the platform equivalent
+pub fn default_profile_path() -> Option<PathBuf> {
+    let data_dir = if let Some(dir) = std::env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(dir)
+    } else if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support")
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".local/share")
+    };
+    Some(data_dir.join("extreme-3d-platformer").join("profile.ron"))
+}
+
+fn load_profile(path: Res<ProfilePath>, mut profile: ResMut<Profile>) {
+    let Some(path) = &path.0 else {
+        return;
+    };
+    match fs::read_to_string(path) {
+        Ok(contents) => match ron::from_str::<Profile>(&contents) {
+            Ok(loaded) => {
+                info!("💾 Loaded profile for {} from {}", loaded.player_name, path.display());
+                *profile = loaded;
+            }
+            Err(err) => warn!("Ignoring unreadable profile {}: {}", path.display(), err),
+        },
+        Err(_) => info!("💾 No saved profile yet, starting fresh"),
+    }
+}
+
+pub fn save_profile(path: &ProfilePath, profile: &Profile) {
+    let Some(path) = &path.0 else {
+        return;
+    };
+    let contents = match ron::ser::to_string_pretty(profile, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Could not serialize profile: {}", err);
+            return;
+        }
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create save directory {}: {}", dir.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = fs::write(path, contents) {
+        warn!("Could not write profile {}: {}", path.display(), err);
+    }
+}
+
+// ===== RUN CLOCK =====
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RunClock {
+    pub run_seconds: f32,
+    pub level_seconds: f32,
+}
+
+fn tick_run_clock(time: Res<Time>, mut clock: ResMut<RunClock>) {
+    clock.run_seconds += time.delta_seconds();
+    clock.level_seconds += time.delta_seconds();
+}
+
+pub fn format_time(seconds: f32) -> String {
+    let minutes = (seconds / 60.0) as u32;
+    format!("{}:{:05.2}", minutes, seconds - minutes as f32 * 60.0)
+}
+
+// ===== RECORDING SYSTEMS =====
+pub fn record_level_complete(
+    game_state: Res<GameState>,
+    path: Res<ProfilePath>,
+    mut profile: ResMut<Profile>,
+    mut clock: ResMut<RunClock>,
+    editor: Option<Res<LevelEditor>>,
+    mut best_events: EventWriter<NewBestTime>,
+) {
+    let completed_level = game_state.level - 1;
+    let level_seconds = std::mem::take(&mut clock.level_seconds);
+    if !not_play_testing(editor) {
+        return;
+    }
+
+    if profile.record_level_time(completed_level, level_seconds) {
+        best_events.send(NewBestTime {
+            level: completed_level,
+            seconds: level_seconds,
+        });
+    }
+    profile.highest_level = profile.highest_level.max(game_state.level);
+
+    save_profile(&path, &profile);
+}
+
+pub fn record_game_over(
+    game_state: Res<GameState>,
+    path: Res<ProfilePath>,
+    mut profile: ResMut<Profile>,
+    clock: Res<RunClock>,
+    mut rank_events: EventWriter<LeaderboardRank>,
+) {
+    if let Some(rank) = record_run(&mut profile, &game_state, &clock) {
+        rank_events.send(rank);
+    }
+    save_profile(&path, &profile);
+}
+
+// Adds the current run to the leaderboard and returns its place there, if it
+// made it; the caller saves
+pub fn record_run(profile: &mut Profile, game_state: &GameState, clock: &RunClock) -> Option<LeaderboardRank> {
+    let rank = profile.submit_run(game_state.score, game_state.level, clock.run_seconds)?;
+    Some(LeaderboardRank {
+        rank,
+        score: game_state.score,
+    })
+}
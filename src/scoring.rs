@@ -0,0 +1,284 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::events::GameReset;
+use crate::GameState;
+
+// ===== SCORING PLUGIN =====
+// Points, combos and bonus lives. Collectibles picked up in quick succession
+// build a combo multiplier; clearing a level adds completion, no-damage and time
+// bonuses, itemised on the level transition screen.
+pub struct ScoringPlugin;
+
+impl Plugin for ScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoringRules>()
+            .init_resource::<LevelScore>()
+            .init_resource::<ScoreBreakdown>()
+            .add_systems(Update, reset_level_score);
+    }
+}
+
+// Set from the tuning file
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct ScoringRules {
+    pub life_every: u32,    // A bonus life each time the score passes a multiple of this
+    pub combo_window: f32,  // Seconds after a pickup in which the next one extends the combo
+    pub combo_step: f32,    // Multiplier gained per chained pickup
+    pub max_multiplier: f32,
+    pub completion_points: u32, // Times the level number
+    pub no_damage_points: u32,  // Times the level number, for a level cleared without losing a life
+    pub par_seconds_per_item: f32,
+    pub time_points: u32, // Per second under par
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            life_every: 100,
+            combo_window: 2.0,
+            combo_step: 0.5,
+            max_multiplier: 3.0,
+            completion_points: 25,
+            no_damage_points: 50,
+            par_seconds_per_item: 12.0,
+            time_points: 5,
+        }
+    }
+}
+
+// ===== LEVEL SCORE =====
+// The level in progress; times are on the run clock
+#[derive(Resource, Default, Clone, Debug)]
+pub struct LevelScore {
+    pub started_at: f32,
+    pub item_points: u32,  // Collectible values before multipliers
+    pub combo_points: u32, // Added by multipliers
+    pub combo: u32,        // Pickups in the current chain
+    pub best_combo: u32,
+    pub last_pickup: Option<f32>,
+    pub lives_lost: u32,
+    pub bonus_lives: u32,
+}
+
+impl LevelScore {
+    pub fn starting_at(run_seconds: f32) -> Self {
+        Self {
+            started_at: run_seconds,
+            ..default()
+        }
+    }
+
+    // Multiplier of the `combo`th pickup in a chain
+    pub fn multiplier(rules: &ScoringRules, combo: u32) -> f32 {
+        let chained = combo.saturating_sub(1) as f32;
+        (1.0 + chained * rules.combo_step).min(rules.max_multiplier)
+    }
+
+    // Whether a pickup at `now` would extend the current chain
+    pub fn combo_live(&self, rules: &ScoringRules, now: f32) -> bool {
+        self.last_pickup.is_some_and(|last| now - last <= rules.combo_window)
+    }
+
+    // Scores a collectible worth `value` picked up at `now`; returns the points earned
+    pub fn collect(&mut self, rules: &ScoringRules, value: u32, now: f32) -> u32 {
+        self.combo = if self.combo_live(rules, now) { self.combo + 1 } else { 1 };
+        self.best_combo = self.best_combo.max(self.combo);
+        self.last_pickup = Some(now);
+
+        let points = (value as f32 * Self::multiplier(rules, self.combo)).round() as u32;
+        self.item_points += value;
+        self.combo_points += points.saturating_sub(value);
+        points
+    }
+
+    // Losing a life breaks the combo and the no-damage bonus
+    pub fn take_damage(&mut self) {
+        self.lives_lost += 1;
+        self.combo = 0;
+        self.last_pickup = None;
+    }
+}
+
+// Adds to the score, with a bonus life for every multiple of `life_every` it
+// passes. Returns the lives gained.
+pub fn award(game_state: &mut GameState, rules: &ScoringRules, points: u32) -> u32 {
+    let before = game_state.score;
+    game_state.score += points;
+    if rules.life_every == 0 {
+        return 0;
+    }
+
+    let lives = game_state.score / rules.life_every - before / rules.life_every;
+    game_state.lives += lives;
+    lives
+}
+
+fn reset_level_score(mut reset_events: EventReader<GameReset>, mut level_score: ResMut<LevelScore>) {
+    if reset_events.read().count() > 0 {
+        *level_score = LevelScore::default();
+    }
+}
+
+// ===== LEVEL BREAKDOWN =====
+// How the last cleared level was scored, for the level transition screen
+#[derive(Resource, Default, Clone, Debug)]
+pub struct ScoreBreakdown {
+    pub level: u32,
+    pub item_points: u32,
+    pub combo_points: u32,
+    pub best_combo: u32,
+    pub completion_points: u32,
+    pub no_damage_points: u32, // Zero when a life was lost
+    pub lives_lost: u32,
+    pub time_points: u32,
+    pub seconds: f32,
+    pub par_seconds: f32,
+    pub bonus_lives: u32, // From thresholds passed during the level and its completion
+}
+
+impl ScoreBreakdown {
+    pub fn bonus_points(&self) -> u32 {
+        self.completion_points + self.no_damage_points + self.time_points
+    }
+
+    pub fn total(&self) -> u32 {
+        self.item_points + self.combo_points + self.bonus_points()
+    }
+}
+
+// Awards the end of level bonuses for `level`, which had `items` collectibles,
+// and returns the breakdown. Leaves the level score ready for the next level.
+pub fn finish_level(
+    game_state: &mut GameState,
+    level_score: &mut LevelScore,
+    rules: &ScoringRules,
+    level: u32,
+    items: u32,
+    run_seconds: f32,
+) -> ScoreBreakdown {
+    let seconds = run_seconds - level_score.started_at;
+    let par_seconds = items as f32 * rules.par_seconds_per_item;
+    let mut breakdown = ScoreBreakdown {
+        level,
+        item_points: level_score.item_points,
+        combo_points: level_score.combo_points,
+        best_combo: level_score.best_combo,
+        completion_points: level * rules.completion_points,
+        no_damage_points: if level_score.lives_lost == 0 { level * rules.no_damage_points } else { 0 },
+        lives_lost: level_score.lives_lost,
+        time_points: (par_seconds - seconds).max(0.0) as u32 * rules.time_points,
+        seconds,
+        par_seconds,
+        bonus_lives: level_score.bonus_lives,
+    };
+    breakdown.bonus_lives += award(game_state, rules, breakdown.bonus_points());
+
+    *level_score = LevelScore::starting_at(run_seconds);
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_state(score: u32) -> GameState {
+        GameState {
+            score,
+            lives: 3,
+            level: 1,
+            collectibles_in_level: 8,
+        }
+    }
+
+    #[test]
+    fn multiplier_grows_per_chained_pickup_up_to_the_cap() {
+        let rules = ScoringRules::default();
+        assert_eq!(LevelScore::multiplier(&rules, 1), 1.0);
+        assert_eq!(LevelScore::multiplier(&rules, 2), 1.5);
+        assert_eq!(LevelScore::multiplier(&rules, 5), 3.0);
+        assert_eq!(LevelScore::multiplier(&rules, 50), rules.max_multiplier);
+    }
+
+    #[test]
+    fn pickups_inside_the_window_chain() {
+        let rules = ScoringRules::default();
+        let mut score = LevelScore::starting_at(0.0);
+
+        assert_eq!(score.collect(&rules, 10, 1.0), 10);
+        assert_eq!(score.collect(&rules, 10, 1.0 + rules.combo_window), 15);
+        assert_eq!(score.collect(&rules, 10, 4.0), 20);
+        assert_eq!(score.combo, 3);
+
+        // Too late: a new chain
+        assert_eq!(score.collect(&rules, 10, 4.0 + rules.combo_window + 0.1), 10);
+        assert_eq!(score.combo, 1);
+        assert_eq!(score.best_combo, 3);
+        assert_eq!(score.item_points, 40);
+        assert_eq!(score.combo_points, 15);
+    }
+
+    #[test]
+    fn damage_breaks_the_chain() {
+        let rules = ScoringRules::default();
+        let mut score = LevelScore::starting_at(0.0);
+        score.collect(&rules, 10, 1.0);
+        score.take_damage();
+
+        assert!(!score.combo_live(&rules, 1.5));
+        assert_eq!(score.collect(&rules, 10, 1.5), 10);
+        assert_eq!(score.lives_lost, 1);
+    }
+
+    #[test]
+    fn award_gives_a_life_per_threshold_crossed() {
+        let rules = ScoringRules::default();
+        let mut state = game_state(90);
+
+        assert_eq!(award(&mut state, &rules, 5), 0);
+        assert_eq!(award(&mut state, &rules, 215), 3); // 95 -> 310 passes 100, 200 and 300
+        assert_eq!(state.score, 310);
+        assert_eq!(state.lives, 6);
+
+        let no_lives = ScoringRules { life_every: 0, ..rules };
+        assert_eq!(award(&mut state, &no_lives, 500), 0);
+        assert_eq!(state.lives, 6);
+    }
+
+    #[test]
+    fn breakdown_totals_every_bonus() {
+        let rules = ScoringRules::default();
+        let mut state = game_state(0);
+        let mut score = LevelScore::starting_at(10.0);
+        score.collect(&rules, 20, 11.0);
+        score.collect(&rules, 20, 12.0);
+
+        // Two items, so 24 s par; cleared in 20 s without damage
+        let breakdown = finish_level(&mut state, &mut score, &rules, 2, 2, 30.0);
+        assert_eq!(breakdown.item_points, 40);
+        assert_eq!(breakdown.combo_points, 10);
+        assert_eq!(breakdown.completion_points, 50);
+        assert_eq!(breakdown.no_damage_points, 100);
+        assert_eq!(breakdown.time_points, 20);
+        assert_eq!(breakdown.bonus_points(), 170);
+        assert_eq!(breakdown.total(), 220);
+        assert_eq!(breakdown.bonus_lives, 1);
+        assert_eq!(state.score, 170); // Item points are awarded as they are collected
+        assert_eq!(score.started_at, 30.0);
+        assert_eq!(score.item_points, 0);
+    }
+
+    #[test]
+    fn losing_a_life_forfeits_the_no_damage_bonus() {
+        let rules = ScoringRules::default();
+        let mut state = game_state(0);
+        let mut score = LevelScore::starting_at(0.0);
+        score.take_damage();
+
+        let breakdown = finish_level(&mut state, &mut score, &rules, 1, 8, 500.0);
+        assert_eq!(breakdown.no_damage_points, 0);
+        assert_eq!(breakdown.time_points, 0); // Over par
+        assert_eq!(breakdown.total(), rules.completion_points);
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode, WindowResolution};
+use serde::{Deserialize, Serialize};
+
+use crate::camera::CameraSettings;
+use crate::save::ProfilePath;
+use crate::MainCamera;
+
+pub const WINDOW_TITLE: &str = "3D Platformer - EXTREME CHALLENGE";
+
+// Choices offered by the options menu; the file may hold any size
+pub const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+pub const WINDOW_MODES: [WindowMode; 3] = [
+    WindowMode::Windowed,
+    WindowMode::BorderlessFullscreen,
+    WindowMode::Fullscreen,
+];
+pub const FOV_RANGE: (f32, f32) = (30.0, 100.0); // Vertical, in degrees
+pub const SMOOTHING_RANGE: (f32, f32) = (0.5, 10.0);
+
+// ===== SETTINGS PLUGIN =====
+// Display, camera and audio settings from `settings.ron` next to the profile.
+// Changing `GameSettings` applies it to the window, camera, lights and volume.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // The windowed binary loads the file itself so the window opens at the right size
+        if !app.world.contains_resource::<GameSettings>() {
+            app.init_resource::<GameSettings>().add_systems(Startup, load_settings);
+        }
+        app.add_systems(
+            Update,
+            (apply_settings.run_if(resource_changed::<GameSettings>()), apply_shadows),
+        );
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct GameSettings {
+    pub window_mode: WindowMode,
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    pub shadows: bool,
+    pub fov_degrees: f32,
+    pub camera_smoothing: f32, // How quickly the camera catches up, per second
+    pub master_volume: f32,    // 0 to 1
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowMode::Windowed,
+            resolution: (1280, 720),
+            vsync: true,
+            shadows: true,
+            fov_degrees: 45.0,
+            camera_smoothing: CameraSettings::default().smoothing,
+            master_volume: 1.0,
+        }
+    }
+}
+
+impl GameSettings {
+    pub fn window(&self) -> Window {
+        Window {
+            title: WINDOW_TITLE.into(),
+            mode: self.window_mode,
+            resolution: WindowResolution::new(self.resolution.0 as f32, self.resolution.1 as f32),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        }
+    }
+
+    // Reads the settings file, writing out the defaults on first run so they can be edited
+    pub fn load(profile_path: &ProfilePath) -> Self {
+        let Some(path) = settings_path(profile_path) else {
+            return Self::default();
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => match ron::from_str::<GameSettings>(&contents) {
+                Ok(loaded) => {
+                    info!("⚙️ Loaded settings from {}", path.display());
+                    loaded
+                }
+                Err(err) => {
+                    warn!("Ignoring unreadable settings {}: {}", path.display(), err);
+                    Self::default()
+                }
+            },
+            Err(_) => {
+                let settings = Self::default();
+                save_settings(profile_path, &settings);
+                settings
+            }
+        }
+    }
+}
+
+// ===== PERSISTENCE =====
+fn settings_path(profile_path: &ProfilePath) -> Option<PathBuf> {
+    Some(profile_path.0.as_ref()?.parent()?.join("settings.ron"))
+}
+
+fn load_settings(profile_path: Res<ProfilePath>, mut settings: ResMut<GameSettings>) {
+    *settings = GameSettings::load(&profile_path);
+}
+
+pub fn save_settings(profile_path: &ProfilePath, settings: &GameSettings) {
+    let Some(path) = settings_path(profile_path) else {
+        return;
+    };
+    let contents = match ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Could not serialize settings: {}", err);
+            return;
+        }
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create config directory {}: {}", dir.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = fs::write(&path, contents) {
+        warn!("Could not write settings {}: {}", path.display(), err);
+    }
+}
+
+// ===== APPLYING SETTINGS =====
+fn apply_settings(
+    settings: Res<GameSettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut projection_query: Query<&mut Projection, With<MainCamera>>,
+    mut camera_settings: ResMut<CameraSettings>,
+    global_volume: Option<ResMut<GlobalVolume>>,
+) {
+    for mut window in &mut window_query {
+        window.mode = settings.window_mode;
+        window
+            .resolution
+            .set(settings.resolution.0 as f32, settings.resolution.1 as f32);
+        window.present_mode = settings.present_mode();
+    }
+
+    for mut projection in &mut projection_query {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = settings.fov_degrees.to_radians();
+        }
+    }
+
+    camera_settings.smoothing = settings.camera_smoothing;
+
+    // Bevy only reads the global volume when a sound starts playing
+    if let Some(mut global_volume) = global_volume {
+        *global_volume = GlobalVolume::new(settings.master_volume.clamp(0.0, 1.0));
+    }
+}
+
+// Every level brings its own sun, so new lights are checked too
+fn apply_shadows(settings: Res<GameSettings>, mut lights: Query<&mut DirectionalLight>) {
+    for mut light in &mut lights {
+        if light.shadows_enabled != settings.shadows {
+            light.shadows_enabled = settings.shadows;
+        }
+    }
+}
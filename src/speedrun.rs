@@ -0,0 +1,364 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::editor::not_play_testing;
+use crate::events::PersonalBest;
+use crate::save::{format_time, record_game_over, Profile, ProfilePath, RunClock};
+use crate::AppState;
+
+// Completed splits listed under the run timer; older ones scroll off
+pub const SHOWN_SPLITS: usize = 5;
+
+// ===== SPEEDRUN PLUGIN =====
+// Splits the run clock at every level clear and compares the run against the
+// personal best for its difficulty. Personal bests are exported to LiveSplit
+// files under `splits/` next to the profile.
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSplits>()
+            .add_systems(Startup, spawn_split_hud)
+            .add_systems(OnEnter(AppState::GameOver), finish_splits.run_if(not_play_testing).before(record_game_over))
+            .add_systems(Update, update_split_hud);
+    }
+}
+
+// ===== SPLIT DATA =====
+// The run in progress; `check_level_complete` adds a split per cleared level
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RunSplits {
+    pub times: Vec<f32>, // Run clock when each level was cleared
+}
+
+impl RunSplits {
+    pub fn split(&mut self, run_seconds: f32) {
+        self.times.push(run_seconds);
+    }
+
+    pub fn segment(&self, index: usize) -> Option<f32> {
+        segment(&self.times, index)
+    }
+}
+
+// Saved per difficulty in the profile
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SplitRecord {
+    pub personal_best: Vec<f32>, // Split times of the best run
+    pub best_segments: Vec<f32>, // Fastest clear of each level across all runs
+    pub attempts: u32,
+}
+
+impl SplitRecord {
+    // A run is better if it clears more levels, or as many in less time
+    pub fn is_better(&self, times: &[f32]) -> bool {
+        match times.len().cmp(&self.personal_best.len()) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => times.last() < self.personal_best.last(),
+        }
+    }
+
+    // Folds a finished run in; returns true if it is the new personal best
+    pub fn add_run(&mut self, times: &[f32]) -> bool {
+        self.attempts += 1;
+        for index in 0..times.len() {
+            let Some(time) = segment(times, index) else {
+                continue;
+            };
+            match self.best_segments.get_mut(index) {
+                Some(best) => *best = best.min(time),
+                None => self.best_segments.push(time),
+            }
+        }
+
+        let improved = self.is_better(times);
+        if improved {
+            self.personal_best = times.to_vec();
+        }
+        improved
+    }
+}
+
+fn segment(times: &[f32], index: usize) -> Option<f32> {
+    let end = *times.get(index)?;
+    let start = index.checked_sub(1).map_or(0.0, |previous| times[previous]);
+    Some(end - start)
+}
+
+// Ends the run's splits: updates the record for `difficulty` and re-exports it.
+// Returns the personal best if the run set one. Leaves the splits empty; the
+// caller saves the profile.
+pub fn finish_run(
+    profile: &mut Profile,
+    splits: &mut RunSplits,
+    difficulty: Difficulty,
+    profile_path: &ProfilePath,
+) -> Option<PersonalBest> {
+    let times = std::mem::take(&mut splits.times);
+    let seconds = *times.last()?;
+
+    let record = profile.splits.entry(difficulty).or_default();
+    let best = record.add_run(&times).then_some(PersonalBest {
+        difficulty,
+        levels: times.len(),
+        seconds,
+    });
+    export_livesplit(profile_path, difficulty, record);
+    best
+}
+
+fn finish_splits(
+    mut profile: ResMut<Profile>,
+    mut splits: ResMut<RunSplits>,
+    difficulty: Res<Difficulty>,
+    profile_path: Res<ProfilePath>,
+    mut best_events: EventWriter<PersonalBest>,
+) {
+    if let Some(best) = finish_run(&mut profile, &mut splits, *difficulty, &profile_path) {
+        best_events.send(best);
+    }
+}
+
+// ===== LIVESPLIT EXPORT =====
+fn splits_path(profile_path: &ProfilePath, difficulty: Difficulty) -> Option<PathBuf> {
+    let file = format!("{}.lss", difficulty.name().to_lowercase());
+    Some(profile_path.0.as_ref()?.parent()?.join("splits").join(file))
+}
+
+// LiveSplit's `hh:mm:ss.fffffff`
+fn livesplit_time(seconds: f32) -> String {
+    let ticks = (seconds as f64 * 10_000_000.0).round() as u64;
+    let total_seconds = ticks / 10_000_000;
+    format!(
+        "{:02}:{:02}:{:02}.{:07}",
+        total_seconds / 3600,
+        total_seconds / 60 % 60,
+        total_seconds % 60,
+        ticks % 10_000_000
+    )
+}
+
+// The run clock skips pauses and level transitions, so it is written as both
+// real time and game time
+pub fn livesplit_document(difficulty: Difficulty, record: &SplitRecord) -> String {
+    let time = |seconds: f32| {
+        let time = livesplit_time(seconds);
+        format!("<RealTime>{}</RealTime><GameTime>{}</GameTime>", time, time)
+    };
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Run version=\"1.7.0\">\n");
+    xml.push_str("  <GameIcon />\n  <GameName>Extreme 3D Platformer</GameName>\n");
+    let _ = writeln!(xml, "  <CategoryName>{}</CategoryName>", difficulty.name());
+    xml.push_str("  <Offset>00:00:00</Offset>\n");
+    let _ = writeln!(xml, "  <AttemptCount>{}</AttemptCount>", record.attempts);
+    xml.push_str("  <AttemptHistory />\n  <Segments>\n");
+    for (index, split) in record.personal_best.iter().enumerate() {
+        xml.push_str("    <Segment>\n");
+        let _ = writeln!(xml, "      <Name>Level {}</Name>", index + 1);
+        xml.push_str("      <Icon />\n      <SplitTimes>\n");
+        let _ = writeln!(
+            xml,
+            "        <SplitTime name=\"Personal Best\">{}</SplitTime>",
+            time(*split)
+        );
+        xml.push_str("      </SplitTimes>\n");
+        if let Some(best) = record.best_segments.get(index) {
+            let _ = writeln!(xml, "      <BestSegmentTime>{}</BestSegmentTime>", time(*best));
+        }
+        xml.push_str("      <SegmentHistory />\n    </Segment>\n");
+    }
+    xml.push_str("  </Segments>\n  <AutoSplitterSettings />\n</Run>\n");
+    xml
+}
+
+fn export_livesplit(profile_path: &ProfilePath, difficulty: Difficulty, record: &SplitRecord) {
+    let Some(path) = splits_path(profile_path, difficulty) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(err) = fs::create_dir_all(dir) {
+            warn!("Could not create splits directory {}: {}", dir.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = fs::write(&path, livesplit_document(difficulty, record)) {
+        warn!("Could not write splits {}: {}", path.display(), err);
+    }
+}
+
+// ===== SPLIT HUD =====
+#[derive(Component)]
+pub struct SplitHud;
+
+fn spawn_split_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 32.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_text_alignment(TextAlignment::Right)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        }),
+        SplitHud,
+    ));
+}
+
+fn format_delta(seconds: f32) -> String {
+    let sign = if seconds < 0.0 { '-' } else { '+' };
+    if seconds.abs() < 60.0 {
+        format!("{}{:.2}", sign, seconds.abs())
+    } else {
+        format!("{}{}", sign, format_time(seconds.abs()))
+    }
+}
+
+// Run timer, then the latest splits with their delta to the personal best:
+// green when ahead, red when behind, gold for a segment faster than any before.
+// The sections are only rebuilt when the splits change or the level in progress
+// falls behind; otherwise the running times are updated in place.
+fn update_split_hud(
+    clock: Res<RunClock>,
+    splits: Res<RunSplits>,
+    profile: Res<Profile>,
+    difficulty: Res<Difficulty>,
+    mut hud_query: Query<&mut Text, With<SplitHud>>,
+    mut was_behind: Local<bool>,
+) {
+    let record = profile.splits.get(&difficulty);
+    let personal_best = record.map_or(&[][..], |record| record.personal_best.as_slice());
+
+    // The level in progress only shows a delta once it has fallen behind
+    let current = splits.times.len();
+    let behind = personal_best.get(current).filter(|pb| clock.run_seconds > **pb);
+    let rebuild = splits.is_changed()
+        || profile.is_changed()
+        || difficulty.is_changed()
+        || *was_behind != behind.is_some();
+    *was_behind = behind.is_some();
+
+    for mut text in &mut hud_query {
+        if rebuild || text.sections.is_empty() {
+            text.sections = split_sections(&splits, record, clock.run_seconds);
+            continue;
+        }
+
+        text.sections[0].value = format!("⏱️ {}", format_time(clock.run_seconds));
+        if let Some(pb) = behind {
+            let count = text.sections.len();
+            text.sections[count - 2].value = format!("\nL{} {}", current + 1, format_time(clock.run_seconds));
+            text.sections[count - 1].value = format!(" {}", format_delta(clock.run_seconds - pb));
+        }
+    }
+}
+
+fn split_sections(splits: &RunSplits, record: Option<&SplitRecord>, run_seconds: f32) -> Vec<TextSection> {
+    let personal_best = record.map_or(&[][..], |record| record.personal_best.as_slice());
+    let best_segments = record.map_or(&[][..], |record| record.best_segments.as_slice());
+    let style = |font_size: f32, color: Color| TextStyle {
+        font_size,
+        color,
+        ..default()
+    };
+
+    let mut sections = vec![TextSection::new(
+        format!("⏱️ {}", format_time(run_seconds)),
+        style(32.0, Color::WHITE),
+    )];
+
+    let first_shown = splits.times.len().saturating_sub(SHOWN_SPLITS);
+    for (index, time) in splits.times.iter().enumerate().skip(first_shown) {
+        sections.push(TextSection::new(
+            format!("\nL{} {}", index + 1, format_time(*time)),
+            style(22.0, Color::WHITE),
+        ));
+        let Some(pb) = personal_best.get(index) else {
+            continue;
+        };
+        let gold = splits
+            .segment(index)
+            .zip(best_segments.get(index))
+            .is_some_and(|(segment, best)| segment < *best);
+        let color = match time - pb {
+            _ if gold => Color::GOLD,
+            delta if delta < 0.0 => Color::GREEN,
+            _ => Color::RED,
+        };
+        sections.push(TextSection::new(format!(" {}", format_delta(time - pb)), style(22.0, color)));
+    }
+
+    let current = splits.times.len();
+    if let Some(pb) = personal_best.get(current).filter(|pb| run_seconds > **pb) {
+        sections.push(TextSection::new(
+            format!("\nL{} {}", current + 1, format_time(run_seconds)),
+            style(22.0, Color::GRAY),
+        ));
+        sections.push(TextSection::new(
+            format!(" {}", format_delta(run_seconds - pb)),
+            style(22.0, Color::RED),
+        ));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn livesplit_time_uses_ticks() {
+        assert_eq!(livesplit_time(0.0), "00:00:00.0000000");
+        assert_eq!(livesplit_time(1.5), "00:00:01.5000000");
+        assert_eq!(livesplit_time(3723.25), "01:02:03.2500000");
+    }
+
+    #[test]
+    fn more_levels_or_less_time_is_a_personal_best() {
+        let mut record = SplitRecord::default();
+        assert!(record.add_run(&[30.0, 70.0]));
+        assert!(!record.add_run(&[20.0, 80.0])); // Slower overall, faster first segment
+        assert!(record.add_run(&[40.0, 65.0]));
+        assert!(record.add_run(&[50.0, 90.0, 120.0]));
+        assert!(!record.add_run(&[10.0]));
+
+        assert_eq!(record.personal_best, vec![50.0, 90.0, 120.0]);
+        assert_eq!(record.best_segments, vec![10.0, 25.0, 30.0]);
+        assert_eq!(record.attempts, 5);
+    }
+
+    #[test]
+    fn lss_lists_the_personal_best_and_best_segments() {
+        let record = SplitRecord {
+            personal_best: vec![30.0, 75.5],
+            best_segments: vec![28.0, 45.5],
+            attempts: 7,
+        };
+        let xml = livesplit_document(Difficulty::Casual, &record);
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains("<CategoryName>Casual</CategoryName>"));
+        assert!(xml.contains("<AttemptCount>7</AttemptCount>"));
+        assert_eq!(xml.matches("<Segment>").count(), 2);
+        assert!(xml.contains("<Name>Level 2</Name>"));
+        assert!(xml.contains(
+            "<SplitTime name=\"Personal Best\"><RealTime>00:01:15.5000000</RealTime>\
+             <GameTime>00:01:15.5000000</GameTime></SplitTime>"
+        ));
+        assert!(xml.contains("<BestSegmentTime><RealTime>00:00:28.0000000</RealTime>"));
+        assert!(xml.trim_end().ends_with("</Run>"));
+    }
+}
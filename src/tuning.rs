@@ -0,0 +1,157 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use bevy::utils::BoxedFuture;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::camera::{CameraFraming, CameraSettings};
+use crate::scoring::ScoringRules;
+use crate::{Hazard, JumpSettings, Player};
+
+pub const TUNING_PATH: &str = "game.tuning.ron";
+
+// ===== TUNING PLUGIN =====
+// Gameplay constants read from `assets/game.tuning.ron`. With file watching on,
+// saving the file applies the new values to the running game.
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Tuning>()
+            .init_asset_loader::<TuningLoader>()
+            .init_resource::<Tuning>()
+            .init_resource::<TuningHandle>()
+            .add_systems(Startup, load_tuning)
+            .add_systems(
+                Update,
+                (
+                    update_tuning,
+                    apply_tuning.run_if(resource_changed::<Tuning>()).after(update_tuning),
+                    scale_hazard_hitboxes.after(update_tuning),
+                ),
+            );
+    }
+}
+
+// The asset as loaded, and also the resource holding the values in effect.
+// Until the file has loaded the compiled-in defaults are used.
+#[derive(Asset, Resource, TypePath, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Tuning {
+    pub player_speed: f32,
+    pub jump_force: f32,
+    pub hazard_hitbox_scale: f32, // Hitbox size relative to the visible hazard
+    pub jump: JumpSettings,
+    pub camera: CameraFraming,
+    pub scoring: ScoringRules,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            player_speed: 8.0,
+            jump_force: 12.0,
+            hazard_hitbox_scale: 1.0,
+            jump: JumpSettings::default(),
+            camera: CameraFraming::default(),
+            scoring: ScoringRules::default(),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TuningHandle(pub Handle<Tuning>);
+
+// ===== TUNING LOADER =====
+#[derive(Default)]
+pub struct TuningLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TuningLoaderError {
+    #[error("Could not read tuning file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse tuning file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for TuningLoader {
+    type Asset = Tuning;
+    type Settings = ();
+    type Error = TuningLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let tuning = ron::de::from_bytes::<Tuning>(&bytes)?;
+            Ok(tuning)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tuning.ron"]
+    }
+}
+
+// ===== TUNING SYSTEMS =====
+fn load_tuning(asset_server: Res<AssetServer>, mut handle: ResMut<TuningHandle>) {
+    handle.0 = asset_server.load(TUNING_PATH);
+}
+
+// Copies the file's values into the resource whenever it (re)loads
+fn update_tuning(
+    mut events: EventReader<AssetEvent<Tuning>>,
+    handle: Res<TuningHandle>,
+    assets: Res<Assets<Tuning>>,
+    mut tuning: ResMut<Tuning>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        if let Some(loaded) = assets.get(*id) {
+            if matches!(event, AssetEvent::Modified { .. }) {
+                info!("🔧 Reloaded tuning from {}", TUNING_PATH);
+            }
+            *tuning = loaded.clone();
+        }
+    }
+}
+
+fn apply_tuning(
+    tuning: Res<Tuning>,
+    mut player_query: Query<&mut Player>,
+    mut jump_settings: ResMut<JumpSettings>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut scoring_rules: ResMut<ScoringRules>,
+) {
+    for mut player in &mut player_query {
+        player.speed = tuning.player_speed;
+        player.jump_force = tuning.jump_force;
+    }
+    *jump_settings = tuning.jump;
+    camera_settings.framing = tuning.camera;
+    *scoring_rules = tuning.scoring;
+}
+
+// New hazards get their hitbox on spawn; a tuning change resizes every one
+fn scale_hazard_hitboxes(tuning: Res<Tuning>, mut hazard_query: Query<(Ref<Hazard>, &mut Collider)>) {
+    for (hazard, mut collider) in &mut hazard_query {
+        if hazard.is_added() || tuning.is_changed() {
+            let half_extents = hazard.half_extents * tuning.hazard_hitbox_scale;
+            *collider = Collider::cuboid(half_extents.x, half_extents.y, half_extents.z);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use bevy_rapier3d::prelude::*;
+
+use platformer_3d::audio::SoundEvent;
+use platformer_3d::difficulty::Difficulty;
+use platformer_3d::events::{ItemCollected, PlayerDamaged};
+use platformer_3d::headless::{HeadlessPlugin, ScriptedInput};
+use platformer_3d::level::{LevelData, LevelLibrary};
+use platformer_3d::powerup::{ActiveEffects, PowerUpKind};
+use platformer_3d::replay::{ReplayMode, ReplayPlayer, ReplayPlugin, ReplayRecorder};
+use platformer_3d::{AppState, Collectible, GamePlugin, GameState, Hazard, LevelEntity, Player};
+
+// Long enough for level 1 to load and the player to land on the ground
+const SETTLE_FRAMES: u32 = 600;
+
+// The first hazard in level_1.level.ron
+const LEVEL_1_HAZARD: Vec3 = Vec3::new(4.0, 1.0, 4.0);
+
+// Everything a scenario sent, kept past the events' two-frame lifetime
+#[derive(Resource, Default)]
+struct Sent {
+    sounds: Vec<SoundEvent>,
+    damaged: Vec<PlayerDamaged>,
+    collected: Vec<ItemCollected>,
+}
+
+fn record_events(
+    mut sounds: EventReader<SoundEvent>,
+    mut damaged: EventReader<PlayerDamaged>,
+    mut collected: EventReader<ItemCollected>,
+    mut sent: ResMut<Sent>,
+) {
+    sent.sounds.extend(sounds.read().copied());
+    sent.damaged.extend(damaged.read().copied());
+    sent.collected.extend(collected.read().copied());
+}
+
+// Level 1 as authored on Casual, for the longest invulnerability after a hit, with
+// the player standing on the ground under the spawn point
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin, GamePlugin))
+        .insert_resource(Difficulty::Casual)
+        .init_resource::<Sent>()
+        .add_systems(Last, record_events);
+    for _ in 0..SETTLE_FRAMES {
+        app.update();
+        let loaded = app.world.query::<&Collectible>().iter(&app.world).next().is_some();
+        if loaded && grounded(&mut app) {
+            break;
+        }
+    }
+    assert!(grounded(&mut app), "the player never landed in level 1");
+    *app.world.resource_mut::<Sent>() = Sent::default();
+    app
+}
+
+// Level 1 cleared out of the way: the collectibles are moved far off so the level
+// isn't complete, everything else is removed. Forgets whatever was sent so far.
+fn cleared_app() -> App {
+    let mut app = headless_app();
+    let level: Vec<(Entity, bool)> = app
+        .world
+        .query_filtered::<(Entity, Has<Collectible>), With<LevelEntity>>()
+        .iter(&app.world)
+        .collect();
+    for (entity, collectible) in level {
+        if collectible {
+            app.world.entity_mut(entity).insert(Transform::from_xyz(200.0, 50.0, 200.0));
+        } else {
+            app.world.despawn(entity);
+        }
+    }
+    // Drop back onto the ground if a platform was underfoot
+    step(&mut app, 2);
+    for _ in 0..SETTLE_FRAMES {
+        if grounded(&mut app) {
+            break;
+        }
+        app.update();
+    }
+    *app.world.resource_mut::<Sent>() = Sent::default();
+    app
+}
+
+fn step(app: &mut App, frames: u32) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+fn grounded(app: &mut App) -> bool {
+    app.world.query::<&Player>().single(&app.world).is_grounded
+}
+
+fn player_translation(app: &mut App) -> Vec3 {
+    app.world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(&app.world)
+        .translation
+}
+
+fn player_velocity(app: &mut App) -> Vec3 {
+    app.world.query_filtered::<&Velocity, With<Player>>().single(&app.world).linvel
+}
+
+// Holds `key` for `frames` starting with the next update
+fn hold(app: &mut App, key: KeyCode, frames: u32) {
+    let now = app.world.resource::<FrameCount>().0;
+    let script = app.world.resource::<ScriptedInput>().clone().hold(key, now, now + frames);
+    app.world.insert_resource(script);
+}
+
+// Walks right for a few frames to find which way that is in the world
+fn walking_direction(app: &mut App) -> Vec3 {
+    hold(app, KeyCode::D, 5);
+    step(app, 3);
+    let velocity = player_velocity(app);
+    step(app, 5);
+    Vec3::new(velocity.x, 0.0, velocity.z).normalize()
+}
+
+// Stands the player still at `position`, keeping its height above the ground
+fn place_player(app: &mut App, position: Vec3) {
+    let height = player_translation(app).y;
+    let (mut transform, mut velocity) = app
+        .world
+        .query_filtered::<(&mut Transform, &mut Velocity), With<Player>>()
+        .single_mut(&mut app.world);
+    transform.translation = Vec3::new(position.x, height, position.z);
+    *velocity = Velocity::zero();
+}
+
+// Starts walking right, finds which way that is on screen, then puts `bundle`
+// a few units ahead of the player and keeps walking into it
+fn walk_into(app: &mut App, bundle: impl Bundle) {
+    hold(app, KeyCode::D, 60);
+    step(app, 5);
+    let velocity = player_velocity(app);
+    let ahead = Vec3::new(velocity.x, 0.0, velocity.z).normalize();
+    let position = player_translation(app) + ahead * 2.5;
+    app.world.spawn((TransformBundle::from_transform(Transform::from_translation(position)), bundle));
+    step(app, 120);
+}
+
+fn hazard() -> impl Bundle {
+    (
+        RigidBody::Fixed,
+        Collider::cuboid(1.0, 1.0, 1.0),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        Hazard { half_extents: Vec3::ONE },
+        LevelEntity,
+    )
+}
+
+fn collectible(value: u32) -> impl Bundle {
+    (
+        RigidBody::Fixed,
+        Collider::ball(0.5),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        Collectible { value },
+        LevelEntity,
+    )
+}
+
+// ===== GAMEPLAY =====
+#[test]
+fn walking_into_the_level_1_hazard_costs_exactly_one_life() {
+    let mut app = headless_app();
+    let lives = app.world.resource::<GameState>().lives;
+    let ahead = walking_direction(&mut app);
+    place_player(&mut app, LEVEL_1_HAZARD - ahead * 3.0);
+    hold(&mut app, KeyCode::D, 40);
+    step(&mut app, 120);
+
+    assert_eq!(app.world.resource::<GameState>().lives, lives - 1);
+    let damaged = &app.world.resource::<Sent>().damaged;
+    assert_eq!(damaged.len(), 1);
+    assert!(!damaged[0].absorbed);
+}
+
+#[test]
+fn walking_into_a_hazard_costs_exactly_one_life() {
+    let mut app = cleared_app();
+    let lives = app.world.resource::<GameState>().lives;
+    walk_into(&mut app, hazard());
+
+    assert_eq!(app.world.resource::<GameState>().lives, lives - 1);
+    let damaged = &app.world.resource::<Sent>().damaged;
+    assert_eq!(damaged.len(), 1);
+    assert!(!damaged[0].absorbed);
+    assert_eq!(damaged[0].lives, lives - 1);
+}
+
+#[test]
+fn collecting_an_item_scores_its_value() {
+    let mut app = cleared_app();
+    walk_into(&mut app, collectible(25));
+
+    assert_eq!(app.world.resource::<GameState>().score, 25);
+    let collected = &app.world.resource::<Sent>().collected;
+    assert_eq!(collected.len(), 1);
+    assert_eq!((collected[0].value, collected[0].points, collected[0].combo), (25, 25, 1));
+    assert_eq!(app.world.resource::<Sent>().sounds, vec![SoundEvent::Collect]);
+}
+
+#[test]
+fn standing_still_is_uneventful() {
+    let mut app = cleared_app();
+    let lives = app.world.resource::<GameState>().lives;
+    step(&mut app, 300);
+
+    let sent = app.world.resource::<Sent>();
+    assert!(sent.sounds.is_empty() && sent.damaged.is_empty() && sent.collected.is_empty());
+    assert_eq!(app.world.resource::<GameState>().lives, lives);
+    assert_eq!(app.world.resource::<GameState>().score, 0);
+}
+
+#[test]
+fn a_level_without_collectibles_never_completes() {
+    let mut app = headless_app();
+    // Rebuilt without its collectibles, as if the file had been edited
+    let level_1 = app.world.resource::<LevelLibrary>().get(1).unwrap().id();
+    let mut levels = app.world.resource_mut::<Assets<LevelData>>();
+    let mut data = levels.get(level_1).unwrap().clone();
+    data.collectibles.clear();
+    levels.insert(level_1, data);
+    step(&mut app, 60);
+
+    assert_eq!(app.world.query::<&Collectible>().iter(&app.world).count(), 0);
+    assert_eq!(*app.world.resource::<State<AppState>>().get(), AppState::Playing);
+    assert_eq!(app.world.resource::<GameState>().level, 1);
+}
+
+// ===== SOUNDS =====
+#[test]
+fn jumping_and_landing_make_their_sounds() {
+    let mut app = cleared_app();
+    hold(&mut app, KeyCode::Space, 2);
+    step(&mut app, 120);
+
+    let sounds = &app.world.resource::<Sent>().sounds;
+    assert_eq!(sounds, &[SoundEvent::Jump, SoundEvent::Land]);
+}
+
+#[test]
+fn hazard_hit_makes_the_hit_sound() {
+    let mut app = cleared_app();
+    walk_into(&mut app, hazard());
+
+    let sounds = &app.world.resource::<Sent>().sounds;
+    assert_eq!(sounds.iter().filter(|sound| **sound == SoundEvent::HazardHit).count(), 1);
+    assert!(!sounds.contains(&SoundEvent::ShieldHit));
+}
+
+#[test]
+fn shielded_hit_makes_the_shield_sound_instead() {
+    let mut app = cleared_app();
+    app.world
+        .query_filtered::<&mut ActiveEffects, With<Player>>()
+        .single_mut(&mut app.world)
+        .add(PowerUpKind::Shield, 20.0);
+    walk_into(&mut app, hazard());
+
+    let sounds = &app.world.resource::<Sent>().sounds;
+    assert!(sounds.contains(&SoundEvent::ShieldHit));
+    assert!(!sounds.contains(&SoundEvent::HazardHit));
+    assert_eq!(app.world.resource::<GameState>().lives, Difficulty::Casual.settings().starting_lives);
+}
+
+// ===== REPLAYS =====
+// Uneven frame times, so playback has to step physics exactly as often per frame
+const FRAME_NANOS: [u64; 4] = [7_000_000, 16_666_667, 23_500_001, 33_333_333];
+
+fn vary_frame_times(
+    recorder: Res<ReplayRecorder>,
+    frame_count: Res<FrameCount>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if recorder.started {
+        let nanos = FRAME_NANOS[frame_count.0 as usize % FRAME_NANOS.len()];
+        *strategy = TimeUpdateStrategy::ManualDuration(Duration::from_nanos(nanos));
+    }
+}
+
+fn replay_app(mode: ReplayMode) -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin, ReplayPlugin { mode }, GamePlugin));
+    app
+}
+
+#[test]
+fn a_recorded_run_replays_to_the_same_position() {
+    // Never saved: the recording is taken straight from the resource
+    let mut recording = replay_app(ReplayMode::Record(PathBuf::from("unsaved.rp")));
+    recording.add_systems(First, vary_frame_times.before(TimeSystem));
+    for _ in 0..SETTLE_FRAMES {
+        if recording.world.resource::<ReplayRecorder>().started {
+            break;
+        }
+        recording.update();
+    }
+    assert!(recording.world.resource::<ReplayRecorder>().started, "the recording never started");
+
+    // Walk off and jump partway
+    let now = recording.world.resource::<FrameCount>().0;
+    let script = ScriptedInput::default().hold(KeyCode::D, now, now + 90).hold(KeyCode::Space, now + 30, now + 35);
+    recording.world.insert_resource(script);
+    step(&mut recording, 180);
+    let replay = recording.world.resource::<ReplayRecorder>().replay.clone();
+    let end = player_translation(&mut recording);
+
+    let frames = replay.frames.len();
+    let mut playback = replay_app(ReplayMode::Playback(replay));
+    for _ in 0..SETTLE_FRAMES + frames as u32 {
+        if playback.world.resource::<ReplayPlayer>().cursor == frames {
+            break;
+        }
+        playback.update();
+    }
+    assert_eq!(playback.world.resource::<ReplayPlayer>().cursor, frames, "the replay never finished");
+    assert_eq!(player_translation(&mut playback), end);
+}
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+// A file in the temp directory, removed again when dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(file_name: &str, contents: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("reachability_cli_{}_{}", std::process::id(), file_name));
+        std::fs::write(&path, contents).unwrap();
+        Self(path)
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().unwrap()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+// One platform a short hop off the ground with a collectible on it, and a
+// second collectible at `height` above that
+fn level_file(name: &str, height: f32) -> TempFile {
+    let contents = format!(
+        "(
+    name: \"{}\",
+    spawn_point: (0.0, 2.0, 0.0),
+    lighting: (sun_illuminance: 15000.0, sun_position: (0.0, 80.0, 0.0), point_lights: []),
+    platforms: [
+        (position: (15.0, 1.0, 0.0), size: (3.0, 0.5, 3.0), color: (0.5, 0.5, 0.5)),
+    ],
+    moving_platforms: [],
+    collectibles: [
+        (position: (15.0, 2.5, 0.0), value: 10, color: (1.0, 1.0, 0.0)),
+        (position: (15.0, {:.1}, 0.0), value: 10, color: (1.0, 1.0, 0.0)),
+    ],
+    hazards: [],
+)",
+        name, height
+    );
+    TempFile::new(&format!("{}.level.ron", name), &contents)
+}
+
+fn exit_code(args: &[&str]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_reachability"))
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+#[test]
+fn reachable_level_exits_with_0() {
+    let level = level_file("reachable", 4.0);
+    assert_eq!(exit_code(&[level.path()]), 0);
+    assert_eq!(exit_code(&["--help"]), 0);
+}
+
+#[test]
+fn unreachable_collectible_exits_with_1() {
+    let level = level_file("unreachable", 40.0);
+    assert_eq!(exit_code(&[level.path()]), 1);
+}
+
+#[test]
+fn bad_arguments_and_files_exit_with_2() {
+    assert_eq!(exit_code(&["--bogus"]), 2);
+    assert_eq!(exit_code(&["--seed"]), 2);
+    assert_eq!(exit_code(&["--speed", "fast"]), 2);
+    assert_eq!(exit_code(&["no/such/level.ron"]), 2);
+
+    let broken = TempFile::new("broken.level.ron", "(name: \"Broken\"");
+    assert_eq!(exit_code(&[broken.path()]), 2);
+    assert_eq!(exit_code(&["--tuning", "no/such/game.tuning.ron"]), 2);
+}
+
+#[test]
+fn jump_force_comes_from_the_tuning_file() {
+    let level = level_file("tuned", 4.0);
+    let weak = TempFile::new("weak.tuning.ron", "(jump_force: 4.0)");
+    assert_eq!(exit_code(&["--tuning", weak.path(), level.path()]), 1);
+    // The flag wins over the file wherever it is given
+    assert_eq!(exit_code(&[level.path(), "--jump-force", "12", "--tuning", weak.path()]), 0);
+}